@@ -1,4 +1,4 @@
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
 
 use super::IcalProperty;
 
@@ -53,35 +53,68 @@ pub trait IcalComponent {
         self.get_property(property_kind)
     }
 
-    unsafe fn remove_property_all(&self, kind: ical::icalproperty_kind) -> usize {
-        unsafe fn remove_property_inner(
-            comp: *mut ical::icalcomponent,
-            kind: ical::icalproperty_kind,
-        ) -> usize {
-            let mut count = 0;
-            let mut prop = ical::icalcomponent_get_first_property(comp, kind);
-            while !prop.is_null() {
-                ical::icalcomponent_remove_property(comp, prop);
-                count += 1;
-                prop = ical::icalcomponent_get_current_property(comp);
-            }
-            let mut inner_comp = ical::icalcomponent_get_first_component(
-                comp,
-                ical::icalcomponent_kind_ICAL_ANY_COMPONENT,
-            );
-            while !inner_comp.is_null() {
-                count += remove_property_inner(inner_comp, kind);
-                inner_comp = ical::icalcomponent_get_next_component(
-                    comp,
-                    ical::icalcomponent_kind_ICAL_ANY_COMPONENT,
-                )
+    /// Remove all properties with the given name from this component and its subcomponents and
+    /// return how many were removed. X- properties are matched by their full name. A name that
+    /// contains a NUL byte matches no property.
+    fn remove_properties_by_name(&self, property_name: &str) -> usize {
+        let c_str = match CString::new(property_name) {
+            Ok(c_str) => c_str,
+            Err(_) => return 0,
+        };
+        unsafe {
+            let property_kind = ical::icalproperty_string_to_kind(c_str.as_ptr());
+            if property_kind == ical::icalproperty_kind_ICAL_X_PROPERTY {
+                remove_properties_inner(self.get_ptr(), property_kind, Some(property_name))
+            } else {
+                remove_properties_inner(self.get_ptr(), property_kind, None)
             }
-            count
         }
+    }
+
+    unsafe fn remove_property_all(&self, kind: ical::icalproperty_kind) -> usize {
+        remove_properties_inner(self.get_ptr(), kind, None)
+    }
+}
+
+/// removes and frees properties of the given kind, restricted to the given name for X- properties
+unsafe fn remove_properties_inner(
+    comp: *mut ical::icalcomponent,
+    kind: ical::icalproperty_kind,
+    x_name: Option<&str>,
+) -> usize {
+    // collect first: removing a property moves libical's internal iterator to the next property
+    // of any kind
+    let mut matching = Vec::new();
+    let mut prop = ical::icalcomponent_get_first_property(comp, kind);
+    while !prop.is_null() {
+        let matches = x_name.map_or(true, |x_name| {
+            let prop_x_name = ical::icalproperty_get_x_name(prop);
+            !prop_x_name.is_null()
+                && CStr::from_ptr(prop_x_name)
+                    .to_string_lossy()
+                    .eq_ignore_ascii_case(x_name)
+        });
+        if matches {
+            matching.push(prop);
+        }
+        prop = ical::icalcomponent_get_next_property(comp, kind);
+    }
+    for prop in &matching {
+        ical::icalcomponent_remove_property(comp, *prop);
+        ical::icalproperty_free(*prop);
+    }
 
-        let comp = self.get_ptr();
-        remove_property_inner(comp, kind)
+    let mut count = matching.len();
+    let mut inner_comp =
+        ical::icalcomponent_get_first_component(comp, ical::icalcomponent_kind_ICAL_ANY_COMPONENT);
+    while !inner_comp.is_null() {
+        count += remove_properties_inner(inner_comp, kind, x_name);
+        inner_comp = ical::icalcomponent_get_next_component(
+            comp,
+            ical::icalcomponent_kind_ICAL_ANY_COMPONENT,
+        )
     }
+    count
 }
 
 #[cfg(test)]
@@ -93,7 +126,7 @@ mod tests {
     #[test]
     fn get_property_test() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
         let prop_name = "SUMMARY";
         let prop_value: String = event.get_property_by_name(prop_name).unwrap().get_value();
 
@@ -107,7 +140,7 @@ mod tests {
     fn get_property_test_lastmodified() {
         let cal =
             IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY_LASTMODIFIED, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
         let prop_name = "LAST-MODIFIED";
         let prop_value: String = event.get_property_by_name(prop_name).unwrap().get_value();
 
@@ -129,17 +162,72 @@ mod tests {
     #[test]
     fn get_property_test_negative() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
         let prop_name = "DESCRIPTION";
         let prop = event.get_property_by_name(prop_name);
 
         assert!(prop.is_none());
     }
 
+    #[test]
+    fn remove_properties_by_name_test() {
+        let cal =
+            IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTI_CATEGORIES, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+        assert_eq!(2, event.get_properties_by_name("CATEGORIES").len());
+
+        let count = event.remove_properties_by_name("CATEGORIES");
+
+        assert_eq!(2, count);
+        assert!(event.get_properties_by_name("CATEGORIES").is_empty());
+        assert!(event.get_property_by_name("SUMMARY").is_some());
+    }
+
+    #[test]
+    fn remove_properties_by_name_keeps_following_test() {
+        let cal =
+            IcalVCalendar::from_str(testing::data::TEST_EVENT_LEADING_CATEGORIES, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        let count = event.remove_properties_by_name("CATEGORIES");
+
+        assert_eq!(2, count);
+        assert!(event.get_properties_by_name("CATEGORIES").is_empty());
+        assert!(event.get_property_by_name("DTSTART").is_some());
+        assert!(event.get_property_by_name("DTEND").is_some());
+        assert!(event.get_property_by_name("SUMMARY").is_some());
+        assert!(event.get_property_by_name("RRULE").is_some());
+    }
+
+    #[test]
+    fn remove_properties_by_name_x_property_test() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_X_PROPERTIES, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        let count = event.remove_properties_by_name("X-MICROSOFT-CDO-BUSYSTATUS");
+
+        assert_eq!(1, count);
+        let remaining: Vec<String> = event
+            .get_properties(ical::icalproperty_kind_ICAL_X_PROPERTY)
+            .iter()
+            .map(|prop| prop.get_name())
+            .collect();
+        assert_eq!(vec!["X-APPLE-TRAVEL-ADVISORY-BEHAVIOR"], remaining);
+    }
+
+    #[test]
+    fn remove_properties_by_name_nul_test() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_X_PROPERTIES, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        assert_eq!(0, event.remove_properties_by_name("SUMMARY\0"));
+        assert!(event.get_property_by_name("SUMMARY").is_some());
+    }
+
     #[test]
     fn get_property_by_name_test() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
         let prop_name = "NONSENSE";
         let prop = event.get_property_by_name(prop_name);
 
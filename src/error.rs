@@ -0,0 +1,42 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// Error type for parsing ical data
+#[derive(Debug, PartialEq)]
+pub enum IcalParseError {
+    /// libical could not make sense of the input
+    Unparseable,
+    /// The input was parsed, but is not a VCALENDAR. Contains the kind that was found instead.
+    WrongComponent(String),
+    /// An event in the calendar has no UID
+    MissingUid,
+    /// The events in the calendar violate the specification. Contains libical's error messages.
+    Restriction(Vec<String>),
+    /// The input exceeds the configured `ParseLimits`
+    TooLarge,
+}
+
+impl fmt::Display for IcalParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IcalParseError::Unparseable => write!(f, "could not parse calendar"),
+            IcalParseError::WrongComponent(kind) => {
+                write!(f, "expected VCALENDAR component, got {}", kind)
+            }
+            IcalParseError::MissingUid => write!(f, "missing required property: UID"),
+            IcalParseError::Restriction(errors) => {
+                write!(f, "errors in VEVENT: {}", errors.join(", "))
+            }
+            IcalParseError::TooLarge => write!(f, "calendar exceeds parse limits"),
+        }
+    }
+}
+
+impl Error for IcalParseError {}
+
+impl From<IcalParseError> for io::Error {
+    fn from(error: IcalParseError) -> io::Error {
+        io::Error::new(io::ErrorKind::Other, error)
+    }
+}
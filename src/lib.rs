@@ -35,6 +35,7 @@ lazy_static! {
 
 pub mod component;
 pub mod duration;
+pub mod error;
 pub mod property;
 pub mod time;
 pub mod timezone;
@@ -47,10 +48,14 @@ pub mod testing;
 
 pub use crate::component::IcalComponent;
 pub use crate::duration::IcalDuration;
+pub use crate::error::IcalParseError;
 pub use crate::property::IcalProperty;
 pub use crate::time::IcalTime;
 pub use crate::timezone::IcalTimeZone;
+pub use crate::utils::misc::generate_uid;
 pub use crate::vcalendar::IcalEventIter;
 pub use crate::vcalendar::IcalVCalendar;
+pub use crate::vcalendar::ParseLimits;
+pub use crate::vevent::Attachment;
 pub use crate::vevent::IcalVEvent;
 
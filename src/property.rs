@@ -1,8 +1,10 @@
-use chrono::NaiveDate;
-use std::ffi::CStr;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::ffi::{CStr, CString};
 use std::fmt;
 
 use super::component::IcalComponent;
+use super::IcalTime;
+use super::IcalTimeZone;
 use crate::ical;
 
 /// A property in the ical data
@@ -49,6 +51,18 @@ impl<'a> IcalProperty<'a> {
         }
     }
 
+    pub fn get_parameter(&self, parameter_name: &str) -> Option<String> {
+        unsafe {
+            let c_str = CString::new(parameter_name).unwrap();
+            let ptr = ical::icalproperty_get_parameter_as_string(self.ptr, c_str.as_ptr());
+            if !ptr.is_null() {
+                Some(CStr::from_ptr(ptr).to_string_lossy().into_owned())
+            } else {
+                None
+            }
+        }
+    }
+
     pub fn as_ical_string(&self) -> String {
         unsafe {
             let cstr = CStr::from_ptr(ical::icalproperty_as_ical_string(self.ptr));
@@ -62,4 +76,79 @@ impl<'a> IcalProperty<'a> {
             NaiveDate::from_ymd_opt(date.year, date.month as u32, date.day as u32)
         }
     }
+
+    /// Parse the value as a date-time, respecting a TZID parameter. Floating times are
+    /// interpreted in the local timezone, like in `IcalTime::timestamp`. Returns None for date
+    /// values.
+    pub fn get_value_as_datetime(&self) -> Option<DateTime<Utc>> {
+        let mut time =
+            unsafe { ical::icaltime_from_string(ical::icalproperty_get_value_as_string(self.ptr)) };
+        if unsafe { ical::icaltime_is_null_time(time) } == 1 || time.is_date == 1 {
+            return None;
+        }
+        if let Some(tzid) = self.get_parameter("TZID") {
+            let timezone = self.find_timezone(&tzid)?;
+            time.zone = *timezone;
+        }
+        Some(IcalTime::from(time).into())
+    }
+
+    /// Look up a timezone among the VTIMEZONEs of the enclosing components, falling back to
+    /// libical's builtin timezones
+    fn find_timezone(&self, tzid: &str) -> Option<IcalTimeZone> {
+        unsafe {
+            let tzid_cstr = CString::new(tzid).unwrap();
+            let mut comp = ical::icalproperty_get_parent(self.ptr);
+            while !comp.is_null() {
+                let timezone = ical::icalcomponent_get_timezone(comp, tzid_cstr.as_ptr());
+                if !timezone.is_null() {
+                    return Some(IcalTimeZone::from_ptr_copy(timezone));
+                }
+                comp = ical::icalcomponent_get_parent(comp);
+            }
+        }
+        IcalTimeZone::from_name(tzid).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+    use crate::IcalVCalendar;
+    use chrono::TimeZone;
+
+    #[test]
+    fn get_value_as_datetime_utc_test() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_ONE_MEETING, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+        let prop = event.get_property_by_name("DTSTART").unwrap();
+
+        assert_eq!(
+            Some(Utc.ymd(1997, 3, 24).and_hms(12, 30, 0)),
+            prop.get_value_as_datetime()
+        );
+    }
+
+    #[test]
+    fn get_value_as_datetime_tzid_test() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_WITH_TIMEZONE_COMPONENT, None)
+            .unwrap();
+        let event = cal.get_principal_event().unwrap();
+        let prop = event.get_property_by_name("DTSTART").unwrap();
+
+        assert_eq!(
+            Some(Utc.ymd(2018, 10, 26).and_hms(11, 30, 0)),
+            prop.get_value_as_datetime()
+        );
+    }
+
+    #[test]
+    fn get_value_as_datetime_date_test() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY_ALLDAY, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+        let prop = event.get_property_by_name("DTSTART").unwrap();
+
+        assert!(prop.get_value_as_datetime().is_none());
+    }
 }
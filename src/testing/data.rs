@@ -168,6 +168,146 @@ pub static TEST_EVENT_ONE_MEETING: &str = indoc!(
 "
 );
 
+pub static TEST_EVENT_MULTI_CATEGORIES: &str = indoc!(
+    "
+    BEGIN:VCALENDAR
+    VERSION:2.0
+    PRODID:-//ABC Corporation//NONSGML My Product//EN
+    BEGIN:VEVENT
+    UID:multicategories
+    DTSTAMP:20070423T123432Z
+    DTSTART:20070628T132900
+    DTEND:20070628T142900
+    SUMMARY:Event with categories
+    CATEGORIES:MEETING,PROJECT
+    CATEGORIES:WORK
+    END:VEVENT
+    END:VCALENDAR
+"
+);
+
+pub static TEST_EVENT_LEADING_CATEGORIES: &str = indoc!(
+    "
+    BEGIN:VCALENDAR
+    VERSION:2.0
+    PRODID:-//ABC Corporation//NONSGML My Product//EN
+    BEGIN:VEVENT
+    UID:leadingcategories
+    DTSTAMP:20070423T123432Z
+    CATEGORIES:MEETING,PROJECT
+    DTSTART:20070628T132900
+    CATEGORIES:WORK
+    DTEND:20070628T142900
+    SUMMARY:Event with categories first
+    RRULE:FREQ=WEEKLY;COUNT=3
+    END:VEVENT
+    END:VCALENDAR
+"
+);
+
+pub static TEST_EVENT_ATTACHMENTS: &str = indoc!(
+    "
+    BEGIN:VCALENDAR
+    VERSION:2.0
+    PRODID:-//ABC Corporation//NONSGML My Product//EN
+    BEGIN:VEVENT
+    UID:attachments
+    DTSTAMP:20070423T123432Z
+    DTSTART:20070628T132900
+    DTEND:20070628T142900
+    SUMMARY:Event with attachments
+    ATTACH;FMTTYPE=application/postscript:ftp://example.com/pub/conf/bkgrnd.ps
+    ATTACH;FMTTYPE=text/plain;ENCODING=BASE64;VALUE=BINARY:SGVsbG8gV29ybGQ=
+    END:VEVENT
+    END:VCALENDAR
+"
+);
+
+pub static TEST_EVENT_COMMENTS: &str = indoc!(
+    r"
+    BEGIN:VCALENDAR
+    VERSION:2.0
+    PRODID:-//ABC Corporation//NONSGML My Product//EN
+    BEGIN:VEVENT
+    UID:comments
+    DTSTAMP:20070423T123432Z
+    DTSTART:20070628T132900
+    DTEND:20070628T142900
+    SUMMARY:Event with comments
+    COMMENT:Bring your own snacks.
+    COMMENT:Second floor\, room 204.
+    END:VEVENT
+    END:VCALENDAR
+"
+);
+
+pub static TEST_EVENT_BUSYSTATUS_FREE: &str = indoc!(
+    "
+    BEGIN:VCALENDAR
+    VERSION:2.0
+    PRODID:-//ABC Corporation//NONSGML My Product//EN
+    BEGIN:VEVENT
+    UID:busystatusfree
+    DTSTAMP:20070423T123432Z
+    DTSTART:20070628T132900
+    DTEND:20070628T142900
+    SUMMARY:Optional Meeting
+    X-MICROSOFT-CDO-BUSYSTATUS:FREE
+    END:VEVENT
+    END:VCALENDAR
+"
+);
+
+pub static TEST_EVENT_MINIMAL: &str = indoc!(
+    "
+    BEGIN:VCALENDAR
+    VERSION:2.0
+    PRODID:-//ABC Corporation//NONSGML My Product//EN
+    BEGIN:VEVENT
+    UID:minimalevent
+    DTSTAMP:20070423T123432Z
+    END:VEVENT
+    END:VCALENDAR
+"
+);
+
+pub static TEST_EVENT_X_PROPERTIES: &str = indoc!(
+    "
+    BEGIN:VCALENDAR
+    VERSION:2.0
+    PRODID:-//ABC Corporation//NONSGML My Product//EN
+    BEGIN:VEVENT
+    UID:xproperties
+    DTSTAMP:20070423T123432Z
+    DTSTART:20070628T132900
+    DTEND:20070628T142900
+    SUMMARY:Meeting with vendor extensions
+    X-MICROSOFT-CDO-BUSYSTATUS:BUSY
+    X-APPLE-TRAVEL-ADVISORY-BEHAVIOR:AUTOMATIC
+    END:VEVENT
+    END:VCALENDAR
+"
+);
+
+pub static TEST_EVENT_RELATED_TO: &str = indoc!(
+    "
+    BEGIN:VCALENDAR
+    VERSION:2.0
+    PRODID:-//ABC Corporation//NONSGML My Product//EN
+    BEGIN:VEVENT
+    UID:relatedto
+    DTSTAMP:20070423T123432Z
+    DTSTART:20070628T132900
+    DTEND:20070628T142900
+    SUMMARY:Event with relations
+    RELATED-TO;RELTYPE=PARENT:parent@example.com
+    RELATED-TO;RELTYPE=SIBLING:sibling@example.com
+    RELATED-TO:implicit@example.com
+    END:VEVENT
+    END:VCALENDAR
+"
+);
+
 pub static TEST_EVENT_RECUR: &str = indoc!(
     "
     BEGIN:VCALENDAR
@@ -185,6 +325,24 @@ pub static TEST_EVENT_RECUR: &str = indoc!(
 "
 );
 
+pub static TEST_EVENT_RECUR_EXDATE: &str = indoc!(
+    "
+    BEGIN:VCALENDAR
+    VERSION:2.0
+    PRODID:-//ABC Corporation//NONSGML My Product//EN
+    BEGIN:VEVENT
+    UID:autocryptthursday
+    DTSTART;VALUE=DATE:20181011
+    DURATION:P2D
+    SUMMARY:Autocrypt Thursdays
+    RRULE:FREQ=WEEKLY;COUNT=10
+    EXDATE;VALUE=DATE:20181018,20181025
+    EXDATE;VALUE=DATE:20181115
+    END:VEVENT
+    END:VCALENDAR
+"
+);
+
 pub static TEST_EVENT_WITH_TIMEZONE_COMPONENT: &str = indoc!(
     "
     BEGIN:VCALENDAR
@@ -218,6 +376,147 @@ pub static TEST_EVENT_WITH_TIMEZONE_COMPONENT: &str = indoc!(
 "
 );
 
+pub static TEST_EVENT_RECUR_OVERRIDE: &str = indoc!(
+    "
+    BEGIN:VCALENDAR
+    VERSION:2.0
+    PRODID:-//ABC Corporation//NONSGML My Product//EN
+    BEGIN:VEVENT
+    UID:weeklymeeting
+    DTSTAMP:20181001T120000Z
+    RECURRENCE-ID:20181018T100000Z
+    DTSTART:20181018T110000Z
+    DTEND:20181018T120000Z
+    SUMMARY:Weekly Meeting (moved)
+    END:VEVENT
+    BEGIN:VEVENT
+    UID:weeklymeeting
+    DTSTAMP:20181001T120000Z
+    DTSTART:20181011T100000Z
+    DTEND:20181011T110000Z
+    SUMMARY:Weekly Meeting
+    RRULE:FREQ=WEEKLY;COUNT=10
+    END:VEVENT
+    END:VCALENDAR
+"
+);
+
+pub static TEST_EVENT_WITH_TIMEZONE_ERROR: &str = indoc!(
+    "
+    BEGIN:VCALENDAR
+    VERSION:2.0
+    PRODID:-//PIMUTILS.ORG//NONSGML khal / icalendar //EN
+    BEGIN:VTIMEZONE
+    TZID:Europe/Berlin
+    X-LIC-ERROR:No value for TZURL property. Removing entire property:
+    BEGIN:DAYLIGHT
+    DTSTART;VALUE=DATE-TIME:20180325T030000
+    TZNAME:CEST
+    TZOFFSETFROM:+0100
+    TZOFFSETTO:+0200
+    END:DAYLIGHT
+    BEGIN:STANDARD
+    DTSTART;VALUE=DATE-TIME:20181028T020000
+    TZNAME:CET
+    TZOFFSETFROM:+0200
+    TZOFFSETTO:+0100
+    END:STANDARD
+    END:VTIMEZONE
+    BEGIN:VEVENT
+    SUMMARY:Some Event
+    DTSTART;TZID=Europe/Berlin;VALUE=DATE-TIME:20181026T133000
+    DTEND;TZID=Europe/Berlin;VALUE=DATE-TIME:20181026T160000
+    DTSTAMP;VALUE=DATE-TIME:20181022T145405Z
+    UID:O2G1SKNFDGC1OZ1675I1A9OFQOFZXTNONYNO
+    SEQUENCE:1
+    LOCATION:Some Location
+    END:VEVENT
+    END:VCALENDAR
+"
+);
+
+pub static TEST_TIMEZONE_ONLY: &str = indoc!(
+    "
+    BEGIN:VCALENDAR
+    VERSION:2.0
+    PRODID:-//PIMUTILS.ORG//NONSGML khal / icalendar //EN
+    BEGIN:VTIMEZONE
+    TZID:Europe/Berlin
+    BEGIN:DAYLIGHT
+    DTSTART;VALUE=DATE-TIME:20180325T030000
+    TZNAME:CEST
+    TZOFFSETFROM:+0100
+    TZOFFSETTO:+0200
+    END:DAYLIGHT
+    BEGIN:STANDARD
+    DTSTART;VALUE=DATE-TIME:20181028T020000
+    TZNAME:CET
+    TZOFFSETFROM:+0200
+    TZOFFSETTO:+0100
+    END:STANDARD
+    END:VTIMEZONE
+    END:VCALENDAR
+"
+);
+
+pub static TEST_EVENT_DST_GAP: &str = indoc!(
+    "
+    BEGIN:VCALENDAR
+    VERSION:2.0
+    PRODID:-//ABC Corporation//NONSGML My Product//EN
+    BEGIN:VTIMEZONE
+    TZID:Europe/Berlin
+    BEGIN:DAYLIGHT
+    DTSTART:19700329T020000
+    RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=-1SU
+    TZNAME:CEST
+    TZOFFSETFROM:+0100
+    TZOFFSETTO:+0200
+    END:DAYLIGHT
+    BEGIN:STANDARD
+    DTSTART:19701025T030000
+    RRULE:FREQ=YEARLY;BYMONTH=10;BYDAY=-1SU
+    TZNAME:CET
+    TZOFFSETFROM:+0200
+    TZOFFSETTO:+0100
+    END:STANDARD
+    END:VTIMEZONE
+    BEGIN:VEVENT
+    UID:dstgap
+    DTSTAMP:20190301T120000Z
+    DTSTART;TZID=Europe/Berlin:20190331T023000
+    DTEND;TZID=Europe/Berlin:20190331T040000
+    SUMMARY:Event in the spring-forward gap
+    END:VEVENT
+    END:VCALENDAR
+"
+);
+
+pub static TEST_MULTIPLE_CALENDARS: &str = indoc!(
+    "
+    BEGIN:VCALENDAR
+    VERSION:2.0
+    PRODID:-//ABC Corporation//NONSGML My Product//EN
+    BEGIN:VEVENT
+    UID:firstcalendar
+    DTSTAMP:20190101T120000Z
+    DTSTART;VALUE=DATE:20190105
+    SUMMARY:First
+    END:VEVENT
+    END:VCALENDAR
+    BEGIN:VCALENDAR
+    VERSION:2.0
+    PRODID:-//ABC Corporation//NONSGML My Product//EN
+    BEGIN:VEVENT
+    UID:secondcalendar
+    DTSTAMP:20190101T120000Z
+    DTSTART;VALUE=DATE:20190106
+    SUMMARY:Second
+    END:VEVENT
+    END:VCALENDAR
+"
+);
+
 pub static TEST_MULTIPLE_EVENTS: &str = indoc!(
     "
     BEGIN:VCALENDAR
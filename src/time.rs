@@ -3,7 +3,7 @@ use super::IcalTimeZone;
 use super::TZ_MUTEX;
 use crate::ical;
 use crate::utils::dateutil;
-use chrono::{Date, DateTime, Local, TimeZone, Utc};
+use chrono::{Date, DateTime, Datelike, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
 use std::ffi::{CStr, CString};
 use std::fmt::{Display, Error, Formatter};
 use std::ops::{Add, Deref};
@@ -60,6 +60,16 @@ impl IcalTime {
         IcalTime { time }
     }
 
+    /// Get an IcalTime object for 00:00:00 on the same day, in the same timezone
+    pub fn start_of_day(&self) -> Self {
+        self.and_hms(0, 0, 0)
+    }
+
+    /// Get an IcalTime object for 23:59:59 on the same day, in the same timezone
+    pub fn end_of_day(&self) -> Self {
+        self.and_hms(23, 59, 59)
+    }
+
     /// Get an IcalTime object based on a timestamp
     pub fn from_timestamp(timestamp: i64) -> Self {
         let _lock = TZ_MUTEX.lock();
@@ -70,7 +80,19 @@ impl IcalTime {
     }
 
     /// Get the timestamp representation of the IcalTime object
+    ///
+    /// Floating times, including dates without a TZID, are read as wall clock time in the local
+    /// timezone. All conversions to chrono types go through this, so a floating time sorts at the
+    /// same instant it displays at.
     pub fn timestamp(&self) -> i64 {
+        if self.is_floating() {
+            let local = self
+                .to_naive_datetime()
+                .and_then(|wall_clock| Local.from_local_datetime(&wall_clock).earliest());
+            if let Some(local) = local {
+                return local.timestamp();
+            }
+        }
         let _lock = TZ_MUTEX.lock();
         unsafe { ical::icaltime_as_timet_with_zone(self.time, self.time.zone) }
     }
@@ -87,6 +109,28 @@ impl IcalTime {
         IcalTime { time }
     }
 
+    /// Get the calendar date of the IcalTime object, as it is represented in its own timezone.
+    /// None if the fields don't form a valid date, e.g. for the null time.
+    pub fn to_naive_date(&self) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(self.time.year, self.time.month as u32, self.time.day as u32)
+    }
+
+    /// Get the wall clock time of the IcalTime object, as it is represented in its own timezone.
+    /// None if the fields don't form a valid datetime.
+    pub fn to_naive_datetime(&self) -> Option<NaiveDateTime> {
+        self.to_naive_date()?.and_hms_opt(
+            self.time.hour as u32,
+            self.time.minute as u32,
+            self.time.second as u32,
+        )
+    }
+
+    /// Get whether the IcalTime object is floating, i.e. neither UTC nor bound to a timezone. This
+    /// includes all dates without a TZID.
+    pub fn is_floating(&self) -> bool {
+        self.time.zone.is_null()
+    }
+
     /// Get the timezone for the IcalTime object
     pub fn get_timezone(&self) -> Option<IcalTimeZone> {
         if self.time.zone.is_null() {
@@ -96,6 +140,30 @@ impl IcalTime {
         Some(IcalTimeZone::from_ptr_copy(tz_ptr))
     }
 
+    /// Get the TZID of the timezone for the IcalTime object, "UTC" for UTC times and None for
+    /// floating times. For libical's builtin zones, which carry a prefixed TZID like
+    /// "/freeassociation.sourceforge.net/US/Eastern", this is the plain name, e.g. "US/Eastern".
+    pub fn get_timezone_name(&self) -> Option<String> {
+        if self.time.zone.is_null() {
+            return None;
+        }
+        unsafe {
+            let zone = self.time.zone as *mut ical::icaltimezone;
+            let tzid = ical::icaltimezone_get_tzid(zone);
+            if tzid.is_null() {
+                return None;
+            }
+            let tzid = CStr::from_ptr(tzid).to_string_lossy().into_owned();
+            if tzid.starts_with('/') {
+                let location = ical::icaltimezone_get_location(zone);
+                if !location.is_null() {
+                    return Some(CStr::from_ptr(location).to_string_lossy().into_owned());
+                }
+            }
+            Some(tzid)
+        }
+    }
+
     /// Get a new IcalTime object with a different timezone
     pub fn with_timezone(&self, timezone: &IcalTimeZone) -> IcalTime {
         let _lock = TZ_MUTEX.lock();
@@ -105,6 +173,15 @@ impl IcalTime {
         IcalTime { time }
     }
 
+    /// Get a new IcalTime object shifted by a number of days. Unlike adding an IcalDuration, this
+    /// keeps date objects as date objects.
+    pub fn add_days(&self, days: i64) -> IcalTime {
+        let mut time = self.time;
+        time.day += days as i32;
+        let time = unsafe { ical::icaltime_normalize(time) };
+        IcalTime { time }
+    }
+
     /// Get a new IcalTime object with the day before the day of the current object
     pub fn pred(&self) -> IcalTime {
         let mut time = self.time;
@@ -141,9 +218,40 @@ impl Display for IcalTime {
 impl FromStr for IcalTime {
     type Err = String;
 
+    /// Accepts ISO 8601 dates and datetimes, with or without seconds and with or without a UTC
+    /// offset, as well as the compact form used in icalendar data. Times without an offset are
+    /// floating.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const DATETIME_FORMATS: [&str; 2] = ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M"];
+
+        if let Ok(datetime) = DateTime::parse_from_rfc3339(s) {
+            return Ok(datetime.with_timezone(&Utc).into());
+        }
+        for format in &DATETIME_FORMATS {
+            if let Ok(datetime) = DateTime::parse_from_str(s, &format!("{}%:z", format)) {
+                return Ok(datetime.with_timezone(&Utc).into());
+            }
+            if s.ends_with('Z') {
+                if let Ok(datetime) = NaiveDateTime::parse_from_str(&s[..s.len() - 1], format) {
+                    return Ok(DateTime::<Utc>::from_utc(datetime, Utc).into());
+                }
+            }
+            if let Ok(datetime) = NaiveDateTime::parse_from_str(s, format) {
+                let (hour, minute, second) =
+                    (datetime.hour(), datetime.minute(), datetime.second());
+                return Ok(IcalTime::from(datetime.date()).and_hms(
+                    hour as i32,
+                    minute as i32,
+                    second as i32,
+                ));
+            }
+        }
+        if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+            return Ok(date.into());
+        }
+
         unsafe {
-            let c_str = CString::new(s).unwrap();
+            let c_str = CString::new(s).map_err(|_| format!("Could not parse time {}", s))?;
             let time = ical::icaltime_from_string(c_str.as_ptr());
             if ical::icaltime_is_null_time(time) == 0 {
                 Ok(IcalTime { time })
@@ -204,6 +312,12 @@ impl From<Date<Local>> for IcalTime {
     }
 }
 
+impl From<NaiveDate> for IcalTime {
+    fn from(date: NaiveDate) -> IcalTime {
+        IcalTime::floating_ymd(date.year(), date.month() as i32, date.day() as i32)
+    }
+}
+
 impl From<Date<Utc>> for IcalTime {
     fn from(date: Date<Utc>) -> IcalTime {
         let timestamp = date.and_hms(0, 0, 0).timestamp();
@@ -211,8 +325,17 @@ impl From<Date<Utc>> for IcalTime {
     }
 }
 
+/// Floating dates keep their calendar date, everything else goes through `IcalTime::timestamp`
 impl From<IcalTime> for Date<Local> {
     fn from(time: IcalTime) -> Date<Local> {
+        if time.is_date() && time.is_floating() {
+            let date = time
+                .to_naive_date()
+                .and_then(|date| Local.from_local_date(&date).earliest());
+            if let Some(date) = date {
+                return date;
+            }
+        }
         Local.timestamp(time.timestamp(), 0).date()
     }
 }
@@ -223,8 +346,14 @@ impl From<IcalTime> for DateTime<Local> {
     }
 }
 
+/// Floating dates keep their calendar date, everything else goes through `IcalTime::timestamp`
 impl From<IcalTime> for Date<Utc> {
     fn from(time: IcalTime) -> Date<Utc> {
+        if time.is_date() && time.is_floating() {
+            if let Some(date) = time.to_naive_date() {
+                return Utc.from_utc_date(&date);
+            }
+        }
         Utc.timestamp(time.timestamp(), 0).date()
     }
 }
@@ -238,6 +367,8 @@ impl From<IcalTime> for DateTime<Utc> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing;
+    use crate::IcalVCalendar;
 
     #[test]
     fn test_parse() {
@@ -251,6 +382,50 @@ mod tests {
         assert!(time.is_err());
     }
 
+    #[test]
+    fn test_parse_iso_date() {
+        let time = "2020-01-01".parse::<IcalTime>().unwrap();
+        assert!(time.is_date());
+        assert!(time.is_floating());
+        assert_eq!(Some(NaiveDate::from_ymd(2020, 1, 1)), time.to_naive_date());
+    }
+
+    #[test]
+    fn test_parse_iso_datetime_utc() {
+        let time = "2020-01-01T14:30:00Z".parse::<IcalTime>().unwrap();
+        assert_eq!("20200101T143000Z", time.to_string());
+    }
+
+    #[test]
+    fn test_parse_iso_datetime_offset() {
+        let time = "2020-01-01T16:30:00+02:00".parse::<IcalTime>().unwrap();
+        assert_eq!("20200101T143000Z", time.to_string());
+    }
+
+    #[test]
+    fn test_parse_iso_datetime_floating() {
+        let time = "2020-01-01T14:30:00".parse::<IcalTime>().unwrap();
+        assert!(time.is_floating());
+        assert_eq!("20200101T143000", time.to_string());
+    }
+
+    #[test]
+    fn test_parse_iso_datetime_without_seconds() {
+        let floating = "2020-01-01T14:30".parse::<IcalTime>().unwrap();
+        let utc = "2020-01-01T14:30Z".parse::<IcalTime>().unwrap();
+        let offset = "2020-01-01T16:30+02:00".parse::<IcalTime>().unwrap();
+
+        assert!(floating.is_floating());
+        assert_eq!("20200101T143000", floating.to_string());
+        assert_eq!("20200101T143000Z", utc.to_string());
+        assert_eq!("20200101T143000Z", offset.to_string());
+    }
+
+    #[test]
+    fn test_parse_garbage() {
+        assert!("next tuesday-ish".parse::<IcalTime>().is_err());
+    }
+
     #[test]
     fn test_with_timezone() {
         let utc = IcalTime::utc();
@@ -269,6 +444,95 @@ mod tests {
         assert!(time.get_timezone().is_none());
     }
 
+    #[test]
+    fn test_get_timezone_name() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_WITH_TIMEZONE_COMPONENT, None)
+            .unwrap();
+        let dtstart = cal.get_principal_event().unwrap().get_dtstart().unwrap();
+
+        assert_eq!(
+            Some("Europe/Berlin".to_string()),
+            dtstart.get_timezone_name()
+        );
+    }
+
+    #[test]
+    fn test_get_timezone_name_builtin() {
+        let tz = IcalTimeZone::from_name("US/Eastern").unwrap();
+        let time = IcalTime::utc().with_timezone(&tz);
+
+        assert_eq!(Some("US/Eastern".to_string()), time.get_timezone_name());
+    }
+
+    #[test]
+    fn test_get_timezone_name_utc() {
+        let time = "20130101T010203Z".parse::<IcalTime>().unwrap();
+        assert_eq!(Some("UTC".to_string()), time.get_timezone_name());
+    }
+
+    #[test]
+    fn test_get_timezone_name_floating() {
+        let time = IcalTime::floating_ymd(2018, 02, 03).and_hms(13, 37, 0);
+        assert!(time.get_timezone_name().is_none());
+    }
+
+    #[test]
+    fn test_is_floating() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
+        let dtstart = cal.get_principal_event().unwrap().get_dtstart().unwrap();
+
+        assert!(dtstart.is_floating());
+        assert!(!IcalTime::utc().is_floating());
+    }
+
+    #[test]
+    fn test_floating_keeps_wall_clock() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
+        let dtstart = cal.get_principal_event().unwrap().get_dtstart().unwrap();
+        let wall_clock = NaiveDate::from_ymd(2007, 6, 28).and_hms(13, 29, 0);
+        let expected = Local.from_local_datetime(&wall_clock).unwrap();
+
+        let local: DateTime<Local> = dtstart.clone().into();
+        let utc: DateTime<Utc> = dtstart.clone().into();
+
+        assert_eq!(wall_clock, local.naive_local());
+        assert_eq!(expected.timestamp(), dtstart.timestamp());
+        assert_eq!(expected.timestamp(), local.timestamp());
+        assert_eq!(expected.with_timezone(&Utc), utc);
+    }
+
+    #[test]
+    fn test_floating_date_to_local() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_DTSTART_ONLY_DATE, None).unwrap();
+        let dtstart = cal.get_principal_event().unwrap().get_dtstart().unwrap();
+        assert!(dtstart.is_date());
+        assert!(dtstart.is_floating());
+
+        let date: Date<Local> = dtstart.clone().into();
+        let datetime: DateTime<Local> = dtstart.into();
+
+        assert_eq!(NaiveDate::from_ymd(2007, 6, 28), date.naive_local());
+        assert_eq!(
+            NaiveDate::from_ymd(2007, 6, 28).and_hms(0, 0, 0),
+            datetime.naive_local()
+        );
+    }
+
+    #[test]
+    fn test_floating_date_to_utc() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_DTSTART_ONLY_DATE, None).unwrap();
+        let dtstart = cal.get_principal_event().unwrap().get_dtstart().unwrap();
+        let midnight = NaiveDate::from_ymd(2007, 6, 28).and_hms(0, 0, 0);
+
+        let date: Date<Utc> = dtstart.clone().into();
+
+        assert_eq!(Utc.ymd(2007, 6, 28), date);
+        assert_eq!(
+            Local.from_local_datetime(&midnight).unwrap().timestamp(),
+            dtstart.timestamp()
+        );
+    }
+
     #[test]
     fn test_from_utc_date() {
         let utc_date = Utc.ymd(2014, 01, 01);
@@ -292,6 +556,42 @@ mod tests {
         assert_eq!(Utc.ymd(2014, 02, 02).and_hms(13, 37, 00), datetime);
     }
 
+    #[test]
+    fn test_is_date() {
+        let date = IcalTime::floating_ymd(2018, 02, 03);
+        let datetime = date.and_hms(13, 37, 0);
+
+        assert!(date.is_date());
+        assert!(!datetime.is_date());
+    }
+
+    #[test]
+    fn test_to_naive_date() {
+        let date = IcalTime::floating_ymd(2018, 02, 03);
+        let datetime = date.and_hms(23, 59, 59);
+
+        let expected = Some(NaiveDate::from_ymd(2018, 02, 03));
+        assert_eq!(expected, date.to_naive_date());
+        assert_eq!(expected, datetime.to_naive_date());
+    }
+
+    #[test]
+    fn test_to_naive_date_null_time() {
+        let time = IcalTime::from(unsafe { ical::icaltime_null_time() });
+
+        assert_eq!(None, time.to_naive_date());
+        assert_eq!(None, time.to_naive_datetime());
+    }
+
+    #[test]
+    fn test_null_time_to_local() {
+        let time = IcalTime::from(unsafe { ical::icaltime_null_time() });
+
+        // must not panic, falls back to the timestamp conversion
+        let _ = Date::<Local>::from(time.clone());
+        let _ = DateTime::<Local>::from(time);
+    }
+
     #[test]
     fn test_add() {
         let now = IcalTime::utc();
@@ -302,6 +602,50 @@ mod tests {
         assert_eq!(1357002123 + 123, sum.timestamp());
     }
 
+    #[test]
+    fn test_start_of_day() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_WITH_TIMEZONE_COMPONENT, None)
+            .unwrap();
+        let dtstart = cal.get_principal_event().unwrap().get_dtstart().unwrap();
+
+        let start = dtstart.start_of_day();
+
+        assert_eq!("20181026T000000", start.to_string());
+        assert_eq!(dtstart.get_timezone_name(), start.get_timezone_name());
+    }
+
+    #[test]
+    fn test_end_of_day() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_WITH_TIMEZONE_COMPONENT, None)
+            .unwrap();
+        let dtstart = cal.get_principal_event().unwrap().get_dtstart().unwrap();
+
+        let end = dtstart.end_of_day();
+
+        assert_eq!("20181026T235959", end.to_string());
+        assert_eq!(dtstart.get_timezone_name(), end.get_timezone_name());
+    }
+
+    #[test]
+    fn test_add_days_date() {
+        let date = IcalTime::floating_ymd(2018, 12, 31);
+
+        let next = date.add_days(1);
+
+        assert!(next.is_date());
+        assert_eq!("20190101", next.to_string());
+    }
+
+    #[test]
+    fn test_add_days_datetime() {
+        let time = IcalTime::utc();
+
+        let earlier = time.add_days(-3);
+
+        assert!(!earlier.is_date());
+        assert_eq!("20121229T010203Z", earlier.to_string());
+    }
+
     #[test]
     fn test_pred() {
         let time = IcalTime::utc();
@@ -3,6 +3,7 @@ use std::ffi::{CStr, CString};
 use std::ops::Deref;
 
 use super::IcalTime;
+use super::TZ_MUTEX;
 use crate::utils::dateutil;
 
 /// A type representing a timezone.
@@ -62,6 +63,16 @@ impl IcalTimeZone {
         unsafe { ical::icaltimezone_get_utc_offset(self.timezone, &mut icaltime, &mut is_dst) }
     }
 
+    /// Get the offset from UTC in seconds at the instant given as a UNIX timestamp
+    pub fn get_offset_at_timestamp(&self, timestamp: i64) -> i32 {
+        let mut icaltime = *IcalTime::from_timestamp(timestamp);
+        let mut is_dst = 0;
+        let _lock = TZ_MUTEX.lock();
+        unsafe {
+            ical::icaltimezone_get_utc_offset_of_utc_time(self.timezone, &mut icaltime, &mut is_dst)
+        }
+    }
+
     pub fn ymd(&self, year: i32, month: i32, day: i32) -> IcalTime {
         IcalTime::floating_ymd(year, month, day).with_timezone(&self)
     }
@@ -87,6 +98,15 @@ mod tests {
         assert_eq!(0, offset);
     }
 
+    #[test]
+    fn test_get_offset_at_timestamp() {
+        let tz = IcalTimeZone::from_name("US/Eastern").unwrap();
+
+        // 2014-01-01T12:00:00Z and 2014-07-01T12:00:00Z
+        assert_eq!(-5 * 60 * 60, tz.get_offset_at_timestamp(1388577600));
+        assert_eq!(-4 * 60 * 60, tz.get_offset_at_timestamp(1404216000));
+    }
+
     #[test]
     fn test_ymd() {
         let tz = IcalTimeZone::from_name("US/Eastern").unwrap();
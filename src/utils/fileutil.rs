@@ -8,3 +8,25 @@ pub fn read_file_to_string(path: &Path) -> io::Result<String> {
     file.read_to_string(&mut contents)?;
     Ok(contents)
 }
+
+/// replaces path separators and control characters, so the result is safe to use as a file name
+pub fn sanitize_filename(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_filename_test() {
+        assert_eq!("abc@example.com", sanitize_filename("abc@example.com"));
+        assert_eq!("a_.._b_c_d", sanitize_filename("a/../b\\c\nd"));
+    }
+}
@@ -1,5 +1,8 @@
 use std::fmt::Display;
 use std::time;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+use uuid::Uuid;
 
 pub fn joinlines(first: &str, second: &str) -> String {
     use itertools::Itertools;
@@ -14,6 +17,27 @@ pub fn joinlines(first: &str, second: &str) -> String {
         .join("\n")
 }
 
+pub fn generate_uid() -> String {
+    format!("{}@khaleesi", Uuid::new_v4().to_hyphenated())
+}
+
+/// 64-bit FNV-1a hash. Unlike std's DefaultHasher its output is fixed, so it can be stored.
+pub fn fnv1a_64(data: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    data.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// lowercases text and strips diacritics, for lenient text matching
+pub fn fold_for_matching(text: &str) -> String {
+    text.nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .collect::<String>()
+        .to_lowercase()
+}
+
 pub fn format_duration(duration: &time::Duration) -> impl Display {
     duration.as_millis()
 }
@@ -31,6 +55,30 @@ mod tests {
         assert_eq!(string_from_secs, string_duration);
     }
 
+    #[test]
+    fn generate_uid_test() {
+        let uid = generate_uid();
+        let other_uid = generate_uid();
+
+        let uuid = uid.trim_end_matches("@khaleesi");
+        assert!(uid.ends_with("@khaleesi"));
+        assert!(Uuid::parse_str(uuid).is_ok());
+        assert_ne!(uid, other_uid);
+    }
+
+    #[test]
+    fn fnv1a_64_test() {
+        assert_eq!(0xcbf2_9ce4_8422_2325, fnv1a_64(b""));
+        assert_eq!(0xaf63_dc4c_8601_ec8c, fnv1a_64(b"a"));
+        assert_eq!(0x8594_4171_f739_67e8, fnv1a_64(b"foobar"));
+    }
+
+    #[test]
+    fn fold_for_matching_test() {
+        assert_eq!("montreal", fold_for_matching("Montréal"));
+        assert_eq!("strasse uber", fold_for_matching("STRASSE Über"));
+    }
+
     #[test]
     fn joinlines_test() {
         let first = ["123", "ß", "1234"].join("\n");
@@ -1,12 +1,31 @@
 use std::ffi::{CStr, CString};
-use std::io;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::string::ToString;
 
 use super::IcalComponent;
+use super::IcalParseError;
 use super::IcalTime;
 use super::IcalVEvent;
+use crate::utils::fileutil;
+
+/// Upper bounds on the input accepted by `IcalVCalendar::from_str_with_limits`
+#[derive(Clone, Debug)]
+pub struct ParseLimits {
+    /// maximum input size in bytes
+    pub max_size: usize,
+    /// maximum number of components, counted by their BEGIN lines
+    pub max_components: usize,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        ParseLimits {
+            max_size: 16 * 1024 * 1024,
+            max_components: 100_000,
+        }
+    }
+}
 
 pub struct IcalVCalendar {
     comp: Rc<IcalComponentOwner>,
@@ -48,6 +67,68 @@ impl IcalVCalendar {
         }
     }
 
+    /// Build a new calendar with VERSION and PRODID set, containing deep copies of events and of
+    /// the VTIMEZONEs they reference from their parent calendars
+    pub fn from_events(events: Vec<IcalVEvent>, prodid: &str) -> Self {
+        unsafe {
+            let comp = ical::icalcomponent_new(ical::icalcomponent_kind_ICAL_VCALENDAR_COMPONENT);
+
+            let version_cstr = CString::new("2.0").unwrap();
+            let prop_version = ical::icalproperty_new_version(version_cstr.as_ptr());
+            ical::icalcomponent_add_property(comp, prop_version);
+
+            let prodid_cstr = CString::new(prodid).unwrap();
+            let prop_prodid = ical::icalproperty_new_prodid(prodid_cstr.as_ptr());
+            ical::icalcomponent_add_property(comp, prop_prodid);
+
+            let mut tzids: Vec<String> = Vec::new();
+            for event in &events {
+                let parent = match event.get_parent() {
+                    Some(parent) => parent,
+                    None => continue,
+                };
+                for prop in event.get_properties_all() {
+                    if let Some(tzid) = prop.get_parameter("TZID") {
+                        if !tzids.contains(&tzid)
+                            && IcalVCalendar::copy_vtimezone(comp, parent, &tzid)
+                        {
+                            tzids.push(tzid);
+                        }
+                    }
+                }
+            }
+
+            for event in events {
+                let event_clone = ical::icalcomponent_new_clone(event.get_ptr());
+                ical::icalcomponent_add_component(comp, event_clone);
+            }
+
+            IcalVCalendar::from_ptr(comp)
+        }
+    }
+
+    /// adds a copy of the VTIMEZONE with the given TZID in another calendar to comp
+    unsafe fn copy_vtimezone(
+        comp: *mut ical::icalcomponent,
+        from: &IcalVCalendar,
+        tzid: &str,
+    ) -> bool {
+        let tzid_cstr = match CString::new(tzid) {
+            Ok(tzid_cstr) => tzid_cstr,
+            Err(_) => return false,
+        };
+        let timezone = ical::icalcomponent_get_timezone(from.get_ptr(), tzid_cstr.as_ptr());
+        if timezone.is_null() {
+            return false;
+        }
+        let vtimezone = ical::icaltimezone_get_component(timezone);
+        if vtimezone.is_null() {
+            return false;
+        }
+        ical::icalcomponent_add_component(comp, ical::icalcomponent_new_clone(vtimezone));
+        true
+    }
+
     pub fn shallow_copy(&self) -> Self {
         IcalVCalendar {
             comp: self.comp.clone(),
@@ -67,31 +148,130 @@ impl IcalVCalendar {
     }
 
     //TODO should probably be private
-    pub fn from_str(str: &str, path: Option<&Path>) -> io::Result<Self> {
+    pub fn from_str(str: &str, path: Option<&Path>) -> Result<Self, IcalParseError> {
+        IcalVCalendar::from_str_with_limits(str, path, &ParseLimits::default())
+    }
+
+    /// Like `from_str`, but rejects input exceeding the given limits before handing it to libical
+    pub fn from_str_with_limits(
+        str: &str,
+        path: Option<&Path>,
+        limits: &ParseLimits,
+    ) -> Result<Self, IcalParseError> {
+        IcalVCalendar::check_limits(str, limits)?;
+
         unsafe {
-            let c_str = CString::new(str).unwrap();
+            let c_str = CString::new(str).map_err(|_| IcalParseError::Unparseable)?;
             let parsed_cal = ical::icalparser_parse_string(c_str.as_ptr());
             if parsed_cal.is_null() {
-                return Err(io::Error::new(io::ErrorKind::Other, "calendar has no path"));
+                return Err(IcalParseError::Unparseable);
             }
 
             let kind = ical::icalcomponent_isa(parsed_cal);
             if kind != ical::icalcomponent_kind_ICAL_VCALENDAR_COMPONENT {
-                let kind =
-                    CStr::from_ptr(ical::icalcomponent_kind_to_string(kind)).to_string_lossy();
-                return Err(io::Error::new(
-                    io::ErrorKind::Other,
-                    format!("expected VCALENDAR component, got {}", kind),
-                ));
+                let kind = CStr::from_ptr(ical::icalcomponent_kind_to_string(kind))
+                    .to_string_lossy()
+                    .into_owned();
+                ical::icalcomponent_free(parsed_cal);
+                return Err(IcalParseError::WrongComponent(kind));
             }
 
             let mut cal = IcalVCalendar::from_ptr(parsed_cal);
             cal.path = path.map(|path| path.to_path_buf());
 
+            if cal
+                .events_iter()
+                .any(|event| ical::icalcomponent_get_uid(event.get_ptr()).is_null())
+            {
+                return Err(IcalParseError::MissingUid);
+            }
+
             Ok(cal)
         }
     }
 
+    fn check_limits(str: &str, limits: &ParseLimits) -> Result<(), IcalParseError> {
+        if str.len() > limits.max_size {
+            return Err(IcalParseError::TooLarge);
+        }
+        let component_count = str
+            .lines()
+            .filter(|line| {
+                line.get(..6)
+                    .map_or(false, |prefix| prefix.eq_ignore_ascii_case("BEGIN:"))
+            })
+            .count();
+        if component_count > limits.max_components {
+            return Err(IcalParseError::TooLarge);
+        }
+        Ok(())
+    }
+
+    /// Parse input holding several VCALENDAR objects one after another. Each of them is parsed on
+    /// its own like in `from_str`, anything outside of them is ignored.
+    pub fn from_str_multi(str: &str) -> Result<Vec<Self>, IcalParseError> {
+        IcalVCalendar::from_str_multi_with_limits(str, &ParseLimits::default())
+    }
+
+    /// Like `from_str_multi`, with the limits applying to the input as a whole. Fails if a
+    /// VCALENDAR is not terminated or starts inside another one.
+    pub fn from_str_multi_with_limits(
+        str: &str,
+        limits: &ParseLimits,
+    ) -> Result<Vec<Self>, IcalParseError> {
+        IcalVCalendar::check_limits(str, limits)?;
+
+        let mut blocks = Vec::new();
+        let mut current: Option<Vec<&str>> = None;
+        for line in str.lines() {
+            let trimmed = line.trim_end();
+            if trimmed.eq_ignore_ascii_case("BEGIN:VCALENDAR") {
+                if current.is_some() {
+                    return Err(IcalParseError::Unparseable);
+                }
+                current = Some(Vec::new());
+            }
+            if let Some(ref mut block) = current {
+                block.push(line);
+            }
+            if trimmed.eq_ignore_ascii_case("END:VCALENDAR") {
+                match current.take() {
+                    Some(block) => blocks.push(block.join("\n")),
+                    None => return Err(IcalParseError::Unparseable),
+                }
+            }
+        }
+
+        if current.is_some() || blocks.is_empty() {
+            return Err(IcalParseError::Unparseable);
+        }
+        blocks
+            .iter()
+            .map(|block| IcalVCalendar::from_str_with_limits(block, None, limits))
+            .collect()
+    }
+
+    /// Like `from_str`, but fails if any VEVENT carries X-LIC-ERROR properties.
+    /// Errors on other components, e.g. an embedded VTIMEZONE, are only logged as warnings.
+    pub fn from_str_strict(str: &str, path: Option<&Path>) -> Result<Self, IcalParseError> {
+        let cal = IcalVCalendar::from_str(str, path)?;
+
+        let (event_errors, other_errors) =
+            unsafe { IcalVCalendar::get_errors_by_component(cal.get_ptr()) };
+        for error in other_errors {
+            warn!(
+                "{}: {}",
+                cal.get_path_as_string().unwrap_or_else(|| "".to_string()),
+                error
+            );
+        }
+        if !event_errors.is_empty() {
+            return Err(IcalParseError::Restriction(event_errors));
+        }
+
+        Ok(cal)
+    }
+
     pub fn normalized(self) -> Self {
         unsafe {
             ical::icalcomponent_normalize(self.get_ptr());
@@ -99,13 +279,11 @@ impl IcalVCalendar {
         self
     }
 
+    /// Get the UID of the principal event, or an empty string if the calendar has no events
     pub fn get_uid(&self) -> String {
-        unsafe {
-            let uid_cstr = CStr::from_ptr(ical::icalcomponent_get_uid(
-                self.get_principal_event().get_ptr(),
-            ));
-            uid_cstr.to_string_lossy().into_owned()
-        }
+        self.get_principal_event()
+            .map(|event| event.get_uid())
+            .unwrap_or_default()
     }
 
     pub fn with_uid(mut self, uid: &str) -> Result<Self, String> {
@@ -125,9 +303,8 @@ impl IcalVCalendar {
                 }
             }
         }
-        self.path = self
-            .path
-            .map(|path| path.with_file_name(uid.to_owned() + ".ics"));
+        let filename = fileutil::sanitize_filename(uid) + ".ics";
+        self.path = self.path.map(|path| path.with_file_name(filename));
         Ok(self)
     }
 
@@ -140,7 +317,10 @@ impl IcalVCalendar {
     }
 
     pub fn with_dtstart(self, dtstart: &IcalTime) -> Self {
-        let event = self.get_principal_event();
+        let event = match self.get_principal_event() {
+            Some(event) => event,
+            None => return self,
+        };
         unsafe {
             let timezone = event.get_dtstart().and_then(|x| x.get_timezone());
             match timezone {
@@ -159,7 +339,10 @@ impl IcalVCalendar {
     }
 
     pub fn with_dtend(self, dtend: &IcalTime) -> Self {
-        let event = self.get_principal_event();
+        let event = match self.get_principal_event() {
+            Some(event) => event,
+            None => return self,
+        };
         unsafe {
             let timezone = event.get_dtend().and_then(|x| x.get_timezone());
             match timezone {
@@ -175,7 +358,10 @@ impl IcalVCalendar {
     }
 
     pub fn with_location(self, location: &str) -> Self {
-        let event = self.get_principal_event();
+        let event = match self.get_principal_event() {
+            Some(event) => event,
+            None => return self,
+        };
         unsafe {
             let c_str = CString::new(location).unwrap();
             ical::icalcomponent_set_location(event.get_ptr(), c_str.as_ptr());
@@ -184,7 +370,10 @@ impl IcalVCalendar {
     }
 
     pub fn with_summary(self, summary: &str) -> Self {
-        let event = self.get_principal_event();
+        let event = match self.get_principal_event() {
+            Some(event) => event,
+            None => return self,
+        };
         unsafe {
             let c_str = CString::new(summary).unwrap();
             ical::icalcomponent_set_summary(event.get_ptr(), c_str.as_ptr());
@@ -193,7 +382,10 @@ impl IcalVCalendar {
     }
 
     pub fn with_last_modified_now(self) -> Self {
-        let event = self.get_principal_event();
+        let event = match self.get_principal_event() {
+            Some(event) => event,
+            None => return self,
+        };
         unsafe {
             let now_icaltime = IcalTime::utc();
 
@@ -208,13 +400,51 @@ impl IcalVCalendar {
         self
     }
 
-    pub fn with_remove_property(self, property_name: &str) -> (Self, usize) {
-        let property_kind = unsafe {
-            let c_str = CString::new(property_name).unwrap();
-            ical::icalproperty_string_to_kind(c_str.as_ptr())
+    pub fn with_prodid(self, prodid: &str) -> Self {
+        unsafe {
+            let c_str = CString::new(prodid).unwrap();
+
+            let prodid_kind = ical::icalproperty_kind_ICAL_PRODID_PROPERTY;
+            if let Some(prop) = self.get_property(prodid_kind) {
+                ical::icalproperty_set_prodid(prop.ptr, c_str.as_ptr());
+            } else {
+                let prop_prodid = ical::icalproperty_new_prodid(c_str.as_ptr());
+                ical::icalcomponent_add_property(self.get_ptr(), prop_prodid);
+            }
+        }
+        self
+    }
+
+    pub fn get_method(&self) -> Option<String> {
+        self.get_property(ical::icalproperty_kind_ICAL_METHOD_PROPERTY)
+            .map(|prop| prop.get_value())
+    }
+
+    pub fn with_method(self, method: &str) -> Result<Self, String> {
+        let method_enum = match CString::new(method) {
+            Ok(c_str) => unsafe { ical::icalproperty_string_to_method(c_str.as_ptr()) },
+            Err(_) => ical::icalproperty_method_ICAL_METHOD_NONE,
         };
+        if method_enum == ical::icalproperty_method_ICAL_METHOD_NONE
+            || method_enum == ical::icalproperty_method_ICAL_METHOD_X
+        {
+            return Err(format!("Unknown METHOD: {}", method));
+        }
 
-        let count = unsafe { IcalComponent::remove_property_all(&self, property_kind) };
+        unsafe {
+            let method_kind = ical::icalproperty_kind_ICAL_METHOD_PROPERTY;
+            if let Some(prop) = self.get_property(method_kind) {
+                ical::icalproperty_set_method(prop.ptr, method_enum);
+            } else {
+                let prop_method = ical::icalproperty_new_method(method_enum);
+                ical::icalcomponent_add_property(self.get_ptr(), prop_method);
+            }
+        }
+        Ok(self)
+    }
+
+    pub fn with_remove_property(self, property_name: &str) -> (Self, usize) {
+        let count = self.remove_properties_by_name(property_name);
         (self, count)
     }
 
@@ -263,28 +493,58 @@ impl IcalVCalendar {
         IcalEventIter::from_vcalendar(self)
     }
 
-    pub fn get_first_event(&self) -> IcalVEvent {
+    /// Get all events with the given UID, i.e. the master event and its overrides
+    pub fn events_by_uid(&self, uid: &str) -> Vec<IcalVEvent> {
+        self.events_iter()
+            .filter(|event| event.get_uid() == uid)
+            .collect()
+    }
+
+    pub fn get_first_event(&self) -> Option<IcalVEvent> {
         let event = unsafe {
             ical::icalcomponent_get_first_component(
                 self.get_ptr(),
                 ical::icalcomponent_kind_ICAL_VEVENT_COMPONENT,
             )
         };
-        if self.events_iter().unique_uid_count() > 1 {
-            warn!(
-                "More than one event in file: {}",
-                self.get_path_as_string().unwrap_or_else(|| "".to_string())
-            )
+        if event.is_null() {
+            return None;
         }
-        IcalVEvent::from_ptr_with_parent(event, self)
+        self.warn_on_multiple_uids();
+        Some(IcalVEvent::from_ptr_with_parent(event, self))
     }
 
-    pub(crate) fn get_principal_event(&self) -> IcalVEvent {
-        let mut event = self.get_first_event();
+    /// The principal event is the master event of the calendar, i.e. the one without a
+    /// RECURRENCE-ID. Falls back to the first event if every event is an override, and is None
+    /// if the calendar contains no events at all.
+    pub(crate) fn get_principal_event(&self) -> Option<IcalVEvent> {
+        let master = self
+            .events_iter()
+            .find(|event| !event.has_property_recurrence_id());
+        let mut event = match master {
+            Some(master) => {
+                self.warn_on_multiple_uids();
+                master
+            }
+            None => self.get_first_event()?,
+        };
         if let Some(ref timestamp) = self.instance_timestamp {
             event = event.with_internal_timestamp(timestamp)
         }
-        event
+        Some(event)
+    }
+
+    fn has_multiple_uids(&self) -> bool {
+        self.events_iter().unique_uid_count() > 1
+    }
+
+    fn warn_on_multiple_uids(&self) {
+        if self.has_multiple_uids() {
+            warn!(
+                "More than one event in file: {}",
+                self.get_path_as_string().unwrap_or_else(|| "".to_string())
+            )
+        }
     }
 
     pub fn check_for_errors(&self) -> Option<Vec<String>> {
@@ -332,6 +592,49 @@ impl IcalVCalendar {
         }
     }
 
+    /// splits the X-LIC-ERRORs of comp into those found within its VEVENTs and all others
+    unsafe fn get_errors_by_component(
+        comp: *mut ical::icalcomponent,
+    ) -> (Vec<String>, Vec<String>) {
+        let mut event_errors: Vec<String> = Vec::new();
+        let mut other_errors = IcalVCalendar::get_errors(comp);
+
+        let mut inner_comp = ical::icalcomponent_get_first_component(
+            comp,
+            ical::icalcomponent_kind_ICAL_ANY_COMPONENT,
+        );
+        while !inner_comp.is_null() {
+            let mut errors = IcalVCalendar::get_errors_recursive(inner_comp);
+            let kind = ical::icalcomponent_isa(inner_comp);
+            if kind == ical::icalcomponent_kind_ICAL_VEVENT_COMPONENT {
+                event_errors.append(&mut errors);
+            } else {
+                other_errors.append(&mut errors);
+            }
+            inner_comp = ical::icalcomponent_get_next_component(
+                comp,
+                ical::icalcomponent_kind_ICAL_ANY_COMPONENT,
+            )
+        }
+        (event_errors, other_errors)
+    }
+
+    unsafe fn get_errors_recursive(comp: *mut ical::icalcomponent) -> Vec<String> {
+        let mut output = IcalVCalendar::get_errors(comp);
+        let mut inner_comp = ical::icalcomponent_get_first_component(
+            comp,
+            ical::icalcomponent_kind_ICAL_ANY_COMPONENT,
+        );
+        while !inner_comp.is_null() {
+            output.append(&mut IcalVCalendar::get_errors_recursive(inner_comp));
+            inner_comp = ical::icalcomponent_get_next_component(
+                comp,
+                ical::icalcomponent_kind_ICAL_ANY_COMPONENT,
+            )
+        }
+        output
+    }
+
     unsafe fn get_errors(comp: *mut ical::icalcomponent) -> Vec<String> {
         let mut prop = ical::icalcomponent_get_first_property(
             comp,
@@ -418,7 +721,122 @@ mod tests {
 
     #[test]
     fn test_from_str_event() {
-        assert!(IcalVCalendar::from_str(testing::data::TEST_BARE_EVENT, None).is_err());
+        assert_eq!(
+            Some(IcalParseError::WrongComponent("VEVENT".to_string())),
+            IcalVCalendar::from_str(testing::data::TEST_BARE_EVENT, None).err()
+        );
+    }
+
+    #[test]
+    fn test_from_str_no_uid() {
+        assert_eq!(
+            Some(IcalParseError::MissingUid),
+            IcalVCalendar::from_str(testing::data::TEST_EVENT_NO_UID, None).err()
+        );
+    }
+
+    #[test]
+    fn test_from_str_with_limits() {
+        let limits = ParseLimits {
+            max_components: 2,
+            ..ParseLimits::default()
+        };
+
+        assert_eq!(
+            Some(IcalParseError::TooLarge),
+            IcalVCalendar::from_str_with_limits(
+                testing::data::TEST_EVENT_WITH_TIMEZONE_COMPONENT,
+                None,
+                &limits
+            )
+            .err()
+        );
+        let cal =
+            IcalVCalendar::from_str_with_limits(testing::data::TEST_EVENT_MULTIDAY, None, &limits);
+        assert!(cal.is_ok());
+    }
+
+    #[test]
+    fn test_from_str_with_limits_size() {
+        let limits = ParseLimits {
+            max_size: 16,
+            ..ParseLimits::default()
+        };
+
+        assert_eq!(
+            Some(IcalParseError::TooLarge),
+            IcalVCalendar::from_str_with_limits(testing::data::TEST_EVENT_MULTIDAY, None, &limits)
+                .err()
+        );
+    }
+
+    #[test]
+    fn test_from_str_multi() {
+        let cals = IcalVCalendar::from_str_multi(testing::data::TEST_MULTIPLE_CALENDARS).unwrap();
+
+        let uids: Vec<String> = cals.iter().map(|cal| cal.get_uid()).collect();
+        assert_eq!(vec!["firstcalendar", "secondcalendar"], uids);
+    }
+
+    #[test]
+    fn test_from_str_multi_empty() {
+        assert!(IcalVCalendar::from_str_multi("").is_err());
+    }
+
+    #[test]
+    fn test_from_str_multi_unterminated() {
+        let truncated = testing::data::TEST_MULTIPLE_CALENDARS.trim_end_matches("END:VCALENDAR\n");
+
+        assert_eq!(
+            Some(IcalParseError::Unparseable),
+            IcalVCalendar::from_str_multi(truncated).err()
+        );
+    }
+
+    #[test]
+    fn test_from_str_multi_nested() {
+        let nested = testing::data::TEST_MULTIPLE_CALENDARS.replacen("END:VCALENDAR\n", "", 1);
+
+        assert_eq!(
+            Some(IcalParseError::Unparseable),
+            IcalVCalendar::from_str_multi(&nested).err()
+        );
+    }
+
+    #[test]
+    fn test_from_str_multi_with_limits() {
+        let limits = ParseLimits {
+            max_components: 3,
+            ..ParseLimits::default()
+        };
+
+        // each calendar on its own stays within the limit, both together don't
+        let result = IcalVCalendar::from_str_multi_with_limits(
+            testing::data::TEST_MULTIPLE_CALENDARS,
+            &limits,
+        );
+        assert_eq!(Some(IcalParseError::TooLarge), result.err());
+    }
+
+    #[test]
+    fn test_from_str_strict() {
+        assert!(IcalVCalendar::from_str_strict(testing::data::TEST_EVENT_MULTIDAY, None).is_ok());
+    }
+
+    #[test]
+    fn test_from_str_strict_timezone_error() {
+        let cal =
+            IcalVCalendar::from_str_strict(testing::data::TEST_EVENT_WITH_TIMEZONE_ERROR, None);
+        assert!(cal.is_ok());
+    }
+
+    #[test]
+    fn test_from_str_strict_event_error() {
+        let cal = IcalVCalendar::from_str_strict(testing::data::TEST_EVENT_WITH_X_LIC_ERROR, None);
+        match cal {
+            Err(IcalParseError::Restriction(errors)) => assert!(!errors.is_empty()),
+            _ => panic!("expected restriction error"),
+        }
     }
 
     #[test]
@@ -434,6 +852,65 @@ mod tests {
         assert_eq!(cal.events_iter().count(), 1)
     }
 
+    #[test]
+    fn from_events_test() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_MULTIPLE_EVENTS, None).unwrap();
+        let events = cal.events_iter().collect();
+
+        let prodid = "-//khaleesi//NONSGML khaleesi//EN";
+        let new_cal = IcalVCalendar::from_events(events, prodid);
+
+        assert_eq!(2, new_cal.events_iter().count());
+        assert_eq!(
+            prodid,
+            new_cal.get_property_by_name("PRODID").unwrap().get_value()
+        );
+
+        let reparsed = IcalVCalendar::from_str(&new_cal.to_string(), None).unwrap();
+        assert_eq!(2, reparsed.events_iter().count());
+        assert!(reparsed.check_for_errors().is_none());
+    }
+
+    #[test]
+    fn from_events_timezone_test() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_WITH_TIMEZONE_COMPONENT, None)
+            .unwrap();
+        let events = cal.events_iter().collect();
+
+        let new_cal = IcalVCalendar::from_events(events, "-//khaleesi//NONSGML khaleesi//EN");
+
+        let reparsed = IcalVCalendar::from_str(&new_cal.to_string(), None).unwrap();
+        let vtimezone_kind = ical::icalcomponent_kind_ICAL_VTIMEZONE_COMPONENT;
+        let vtimezone_count =
+            unsafe { ical::icalcomponent_count_components(reparsed.get_ptr(), vtimezone_kind) };
+        assert_eq!(1, vtimezone_count);
+        let event = reparsed.get_principal_event().unwrap();
+        let dtstart = event.get_dtstart().unwrap();
+        assert_eq!(
+            Some("Europe/Berlin".to_string()),
+            dtstart.get_timezone_name()
+        );
+    }
+
+    #[test]
+    fn no_events_test() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_TIMEZONE_ONLY, None).unwrap();
+
+        assert_eq!(0, cal.events_iter().count());
+        assert!(cal.get_first_event().is_none());
+        assert!(cal.get_principal_event().is_none());
+        assert_eq!("", cal.get_uid());
+    }
+
+    #[test]
+    fn no_events_with_summary_test() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_TIMEZONE_ONLY, None).unwrap();
+
+        let new_cal = cal.with_summary("summary");
+
+        assert!(new_cal.get_principal_event().is_none());
+    }
+
     #[test]
     fn load_serialize() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
@@ -452,7 +929,7 @@ mod tests {
     fn with_dtstamp_test() {
         let mut cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
         cal = cal.with_dtstamp_now();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
 
         let dtstamp_prop = ical::icalproperty_kind_ICAL_DTSTAMP_PROPERTY;
         let now_dtstamp = event.get_property(dtstamp_prop).unwrap().get_value();
@@ -508,7 +985,7 @@ mod tests {
             IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY_LASTMODIFIED, None).unwrap();
 
         let new_cal = cal.with_last_modified_now();
-        let event = new_cal.get_principal_event();
+        let event = new_cal.get_principal_event().unwrap();
 
         let last_modified_kind = ical::icalproperty_kind_ICAL_LASTMODIFIED_PROPERTY;
         assert_eq!(
@@ -522,7 +999,7 @@ mod tests {
         let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
 
         let new_cal = cal.with_last_modified_now();
-        let event = new_cal.get_principal_event();
+        let event = new_cal.get_principal_event().unwrap();
 
         let last_modified_kind = ical::icalproperty_kind_ICAL_LASTMODIFIED_PROPERTY;
         assert_eq!(
@@ -538,10 +1015,118 @@ mod tests {
         let location = "test";
         let new_cal = cal.with_location(location);
 
-        let event = new_cal.get_principal_event();
+        let event = new_cal.get_principal_event().unwrap();
         assert_eq!(location, event.get_location().unwrap())
     }
 
+    #[test]
+    fn test_with_dtstart_keeps_tzid() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_WITH_TIMEZONE_COMPONENT, None)
+            .unwrap()
+            .with_dtstart(&IcalTime::floating_ymd(2018, 10, 26).and_hms(15, 0, 0));
+
+        let event = cal.get_principal_event().unwrap();
+        let prop = event
+            .get_property(ical::icalproperty_kind_ICAL_DTSTART_PROPERTY)
+            .unwrap();
+        assert_eq!(
+            Some("Europe/Berlin".to_string()),
+            prop.get_parameter("TZID")
+        );
+        assert_eq!("20181026T150000", prop.get_value());
+    }
+
+    #[test]
+    fn test_with_dtend_keeps_tzid() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_WITH_TIMEZONE_COMPONENT, None)
+            .unwrap()
+            .with_dtend(&IcalTime::floating_ymd(2018, 10, 26).and_hms(17, 0, 0));
+
+        let event = cal.get_principal_event().unwrap();
+        let prop = event
+            .get_property(ical::icalproperty_kind_ICAL_DTEND_PROPERTY)
+            .unwrap();
+        assert_eq!(
+            Some("Europe/Berlin".to_string()),
+            prop.get_parameter("TZID")
+        );
+        assert_eq!("20181026T170000", prop.get_value());
+    }
+
+    #[test]
+    fn test_with_summary() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None)
+            .unwrap()
+            .with_summary("X");
+
+        let event = cal.get_principal_event().unwrap();
+        assert_eq!("X", event.get_summary().unwrap())
+    }
+
+    #[test]
+    fn test_with_prodid() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
+
+        let prodid = "-//khaleesi//NONSGML khaleesi//EN";
+        let new_cal = cal.with_prodid(prodid);
+
+        assert_eq!(
+            prodid,
+            new_cal.get_property_by_name("PRODID").unwrap().get_value()
+        );
+        assert_eq!(1, new_cal.get_properties_by_name("PRODID").len());
+    }
+
+    #[test]
+    fn test_with_prodid_added() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_NO_PRODID, None).unwrap();
+
+        let prodid = "-//khaleesi//NONSGML khaleesi//EN";
+        let new_cal = cal.with_prodid(prodid);
+
+        assert_eq!(
+            prodid,
+            new_cal.get_property_by_name("PRODID").unwrap().get_value()
+        );
+    }
+
+    #[test]
+    fn test_get_method() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_ONE_MEETING, None).unwrap();
+        assert_eq!(Some("xyz".to_string()), cal.get_method());
+    }
+
+    #[test]
+    fn test_get_method_none() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
+        assert_eq!(None, cal.get_method());
+    }
+
+    #[test]
+    fn test_with_method() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_ONE_MEETING, None).unwrap();
+
+        let new_cal = cal
+            .with_method("REQUEST")
+            .unwrap()
+            .with_method("CANCEL")
+            .unwrap();
+
+        assert_eq!(Some("CANCEL".to_string()), new_cal.get_method());
+        assert_eq!(1, new_cal.get_properties_by_name("METHOD").len());
+    }
+
+    #[test]
+    fn test_with_method_invalid() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_ONE_MEETING, None)
+            .unwrap()
+            .with_method("REQUEST")
+            .unwrap();
+
+        assert!(cal.shallow_copy().with_method("NOT A METHOD").is_err());
+        assert_eq!(Some("REQUEST".to_string()), cal.get_method());
+    }
+
     //#[test]
     //fn test_with_internal_timestamp() {
     //let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
@@ -570,6 +1155,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn with_uid_unsafe_filename_test() {
+        let path = PathBuf::from("calendar/event.ics");
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, Some(&path)).unwrap();
+
+        let uid = "../../etc/passwd";
+        let new_cal = cal.with_uid(uid).unwrap();
+
+        assert_eq!(uid, new_cal.get_uid());
+        let new_path = new_cal.get_path().unwrap();
+        assert_eq!(path.parent(), new_path.parent());
+        assert_eq!(PathBuf::from("calendar/.._.._etc_passwd.ics"), *new_path);
+    }
+
     #[test]
     fn with_uid_multiple_test() {
         let path = PathBuf::from("test/path");
@@ -591,10 +1190,40 @@ mod tests {
 
             assert_eq!(1, new_cal.events_iter().count());
             assert_eq!(*uid, new_cal.get_uid());
-            assert_eq!(*uid, new_cal.get_principal_event().get_uid());
+            assert_eq!(*uid, new_cal.get_principal_event().unwrap().get_uid());
         }
     }
 
+    #[test]
+    fn get_principal_event_master_test() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_RECUR_OVERRIDE, None).unwrap();
+
+        assert_eq!(2, cal.events_iter().count());
+        // the condition warn_on_multiple_uids warns on
+        assert!(!cal.has_multiple_uids());
+
+        let event = cal.get_principal_event().unwrap();
+        assert!(!event.has_property_recurrence_id());
+        assert_eq!("Weekly Meeting", event.get_summary().unwrap());
+    }
+
+    #[test]
+    fn has_multiple_uids_test() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_MULTIPLE_EVENTS, None).unwrap();
+
+        assert!(cal.has_multiple_uids());
+    }
+
+    #[test]
+    fn events_by_uid_test() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_RECUR_OVERRIDE, None).unwrap();
+
+        let events = cal.events_by_uid("weeklymeeting");
+
+        assert_eq!(2, events.len());
+        assert!(cal.events_by_uid("otheruid").is_empty());
+    }
+
     #[test]
     fn clone_test() {
         let path = PathBuf::from("test/path");
@@ -1,14 +1,39 @@
-use std::ffi::CStr;
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
 
 use super::IcalComponent;
 use super::IcalDuration;
 use super::IcalTime;
 use super::IcalVCalendar;
 use crate::ical;
+use crate::utils::misc;
+
+/// The value of an ATTACH property
+#[derive(Debug, PartialEq)]
+pub enum Attachment {
+    /// A reference to the attachment, e.g. a URL
+    Uri(String),
+    /// Inline base64-encoded binary data
+    Binary(String),
+}
 
 pub struct IcalVEvent {
     ptr: *mut ical::icalcomponent,
     parent: Option<IcalVCalendar>,
+    recur_cache: RefCell<Option<RecurCache>>,
+}
+
+/// recurrence instances of an event, along with the end of the window and the serialized
+/// recurrence properties they were computed for
+struct RecurCache {
+    window_end: IcalTime,
+    key: String,
+    datetimes: Vec<IcalTime>,
+}
+
+#[cfg(test)]
+thread_local! {
+    static RECUR_COMPUTATIONS: std::cell::Cell<usize> = std::cell::Cell::new(0);
 }
 
 impl Drop for IcalVEvent {
@@ -37,6 +62,7 @@ impl IcalVEvent {
         IcalVEvent {
             ptr,
             parent: Some(parent.shallow_copy()),
+            recur_cache: RefCell::new(None),
             //instance_timestamp: None,
         }
     }
@@ -76,6 +102,19 @@ impl IcalVEvent {
         })
     }
 
+    /// Get the end of the event. Without DTEND or DURATION, an event starting on a date lasts
+    /// that whole day, and one starting at a datetime ends when it starts.
+    pub fn get_end(&self) -> Option<IcalTime> {
+        self.get_dtend().or_else(|| {
+            let dtstart = self.get_dtstart()?;
+            if dtstart.is_date() {
+                Some(dtstart.add_days(1))
+            } else {
+                Some(dtstart + self.get_duration()?)
+            }
+        })
+    }
+
     pub fn get_dtstart(&self) -> Option<IcalTime> {
         unsafe {
             let dtstart = ical::icalcomponent_get_dtstart(self.ptr);
@@ -93,21 +132,58 @@ impl IcalVEvent {
             .is_empty()
     }
 
+    pub fn has_property_recurrence_id(&self) -> bool {
+        self.get_property(ical::icalproperty_kind_ICAL_RECURRENCEID_PROPERTY)
+            .is_some()
+    }
+
+    /// Get the start times of all recurrence instances, up to one year in the future.
+    ///
+    /// The result is cached on this IcalVEvent, keyed on the window and on the DTSTART, RRULE,
+    /// RDATE, EXRULE and EXDATE properties, so it is recomputed whenever any of those change.
     pub fn get_recur_datetimes(&self) -> Vec<IcalTime> {
+        let dtstart = match self.get_dtstart() {
+            Some(dtstart) => dtstart,
+            None => {
+                warn!("Cannot expand recurrences of event without DTSTART");
+                return vec![];
+            }
+        };
+
+        //unroll up to 1 year in the future
+        let mut window_end = unsafe { ical::icaltime_today() };
+        window_end.year += 1;
+        let window_end = IcalTime::from(window_end);
+
+        let key = self.recur_cache_key();
+        if let Some(ref cache) = *self.recur_cache.borrow() {
+            if cache.window_end == window_end && cache.key == key {
+                return cache.datetimes.clone();
+            }
+        }
+
+        let datetimes = self.compute_recur_datetimes(&dtstart, &window_end);
+        *self.recur_cache.borrow_mut() = Some(RecurCache {
+            window_end,
+            key,
+            datetimes: datetimes.clone(),
+        });
+        datetimes
+    }
+
+    fn compute_recur_datetimes(&self, dtstart: &IcalTime, window_end: &IcalTime) -> Vec<IcalTime> {
+        #[cfg(test)]
+        RECUR_COMPUTATIONS.with(|count| count.set(count.get() + 1));
+
         let mut result: Vec<IcalTime> = vec![];
         let result_ptr: *mut ::std::os::raw::c_void =
             &mut result as *mut _ as *mut ::std::os::raw::c_void;
 
-        let dtstart = self.get_dtstart().unwrap();
         unsafe {
-            //unroll up to 1 year in the future
-            let mut dtend = ical::icaltime_today();
-            dtend.year += 1;
-
             ical::icalcomponent_foreach_recurrence(
                 self.ptr,
-                *dtstart,
-                dtend,
+                **dtstart,
+                **window_end,
                 Some(recur_callback),
                 result_ptr,
             );
@@ -120,10 +196,47 @@ impl IcalVEvent {
         result
     }
 
+    /// the serialized properties recurrence expansion depends on
+    fn recur_cache_key(&self) -> String {
+        let kinds = [
+            ical::icalproperty_kind_ICAL_DTSTART_PROPERTY,
+            ical::icalproperty_kind_ICAL_RRULE_PROPERTY,
+            ical::icalproperty_kind_ICAL_RDATE_PROPERTY,
+            ical::icalproperty_kind_ICAL_EXRULE_PROPERTY,
+            ical::icalproperty_kind_ICAL_EXDATE_PROPERTY,
+        ];
+        kinds
+            .iter()
+            .flat_map(|kind| self.get_properties(*kind))
+            .map(|prop| prop.as_ical_string())
+            .collect()
+    }
+
+    /// Get all EXDATEs of the event. Handles both multiple EXDATE properties and
+    /// comma-separated lists of dates within a single property. Recurrence expansion does
+    /// not need these, libical already skips EXDATEs there.
+    pub fn get_exdates(&self) -> Vec<IcalTime> {
+        let mut exdates = vec![];
+        for prop in self.get_properties(ical::icalproperty_kind_ICAL_EXDATE_PROPERTY) {
+            let zone = unsafe { ical::icalproperty_get_exdate(prop.ptr).zone };
+            for value in prop.get_value().split(',') {
+                if let Ok(exdate) = value.trim().parse::<IcalTime>() {
+                    let mut exdate = *exdate;
+                    if exdate.zone.is_null() {
+                        exdate.zone = zone;
+                    }
+                    exdates.push(IcalTime::from(exdate));
+                }
+            }
+        }
+        exdates
+    }
+
     pub fn shallow_copy(&self) -> IcalVEvent {
         IcalVEvent {
             ptr: self.ptr,
             parent: self.parent.as_ref().map(|parent| parent.shallow_copy()),
+            recur_cache: RefCell::new(None),
         }
     }
 
@@ -132,6 +245,7 @@ impl IcalVEvent {
         IcalVEvent {
             ptr: self.ptr,
             parent: self.parent.as_ref().map(|parent| parent.shallow_copy()),
+            recur_cache: RefCell::new(None),
         }
     }
 
@@ -150,6 +264,14 @@ impl IcalVEvent {
         }
     }
 
+    /// Check if the summary contains needle, ignoring case and diacritics
+    pub fn summary_matches(&self, needle: &str) -> bool {
+        let needle = misc::fold_for_matching(needle);
+        self.get_summary().map_or(false, |summary| {
+            misc::fold_for_matching(&summary).contains(&needle)
+        })
+    }
+
     pub fn get_description(&self) -> Option<String> {
         unsafe {
             let ptr = ical::icalcomponent_get_description(self.ptr);
@@ -172,6 +294,99 @@ impl IcalVEvent {
         }
     }
 
+    pub fn get_comments(&self) -> Vec<String> {
+        self.get_properties(ical::icalproperty_kind_ICAL_COMMENT_PROPERTY)
+            .iter()
+            .map(|prop| unsafe {
+                let ptr = ical::icalproperty_get_comment(prop.ptr);
+                CStr::from_ptr(ptr).to_string_lossy().into_owned()
+            })
+            .collect()
+    }
+
+    /// Get the UIDs of related components along with their RELTYPE, which defaults to PARENT
+    pub fn get_related_to(&self) -> Vec<(String, String)> {
+        self.get_properties(ical::icalproperty_kind_ICAL_RELATEDTO_PROPERTY)
+            .iter()
+            .map(|prop| {
+                let uid = unsafe {
+                    let ptr = ical::icalproperty_get_relatedto(prop.ptr);
+                    CStr::from_ptr(ptr).to_string_lossy().into_owned()
+                };
+                let reltype = prop
+                    .get_parameter("RELTYPE")
+                    .unwrap_or_else(|| "PARENT".to_string());
+                (uid, reltype)
+            })
+            .collect()
+    }
+
+    pub fn get_categories(&self) -> Vec<String> {
+        self.get_properties(ical::icalproperty_kind_ICAL_CATEGORIES_PROPERTY)
+            .iter()
+            .flat_map(|prop| {
+                prop.get_value()
+                    .split(',')
+                    .map(|category| category.trim().to_owned())
+                    .collect::<Vec<String>>()
+            })
+            .filter(|category| !category.is_empty())
+            .collect()
+    }
+
+    pub fn add_category(&self, category: &str) {
+        let mut categories = self.get_categories();
+        categories.push(category.to_owned());
+        self.write_categories(&categories);
+    }
+
+    pub fn remove_category(&self, category: &str) {
+        let mut categories = self.get_categories();
+        categories.retain(|existing| existing != category);
+        self.write_categories(&categories);
+    }
+
+    pub fn set_categories(&self, categories: &[&str]) {
+        let categories: Vec<String> = categories.iter().map(|c| (*c).to_owned()).collect();
+        self.write_categories(&categories);
+    }
+
+    /// replaces all CATEGORIES properties with a single one, dropping duplicates
+    fn write_categories(&self, categories: &[String]) {
+        let mut unique: Vec<&str> = Vec::new();
+        for category in categories {
+            if !unique.contains(&category.as_str()) {
+                unique.push(category);
+            }
+        }
+
+        unsafe {
+            self.remove_property_all(ical::icalproperty_kind_ICAL_CATEGORIES_PROPERTY);
+            if !unique.is_empty() {
+                let c_str = CString::new(unique.join(",")).unwrap();
+                let prop = ical::icalproperty_new_categories(c_str.as_ptr());
+                ical::icalcomponent_add_property(self.ptr, prop);
+            }
+        }
+    }
+
+    pub fn get_attachments(&self) -> Vec<Attachment> {
+        self.get_properties(ical::icalproperty_kind_ICAL_ATTACH_PROPERTY)
+            .iter()
+            .map(|prop| {
+                let encoding = prop.get_parameter("ENCODING").unwrap_or_default();
+                let value_type = prop.get_parameter("VALUE").unwrap_or_default();
+                if encoding.eq_ignore_ascii_case("BASE64")
+                    || value_type.eq_ignore_ascii_case("BINARY")
+                {
+                    Attachment::Binary(prop.get_value())
+                } else {
+                    Attachment::Uri(prop.get_value())
+                }
+            })
+            .collect()
+    }
+
     pub fn get_uid(&self) -> String {
         unsafe {
             let cstr = CStr::from_ptr(ical::icalcomponent_get_uid(self.ptr));
@@ -179,6 +394,88 @@ impl IcalVEvent {
         }
     }
 
+    /// Check if the event blocks time. TRANSP takes precedence, falling back to
+    /// X-MICROSOFT-CDO-BUSYSTATUS, and events are busy if neither is given.
+    pub fn is_busy(&self) -> bool {
+        if let Some(transp) = self.get_property(ical::icalproperty_kind_ICAL_TRANSP_PROPERTY) {
+            return transp.get_value() != "TRANSPARENT";
+        }
+        let busystatus = self
+            .get_properties(ical::icalproperty_kind_ICAL_X_PROPERTY)
+            .into_iter()
+            .find(|prop| prop.get_name() == "X-MICROSOFT-CDO-BUSYSTATUS");
+        match busystatus {
+            Some(busystatus) => busystatus.get_value() != "FREE",
+            None => true,
+        }
+    }
+
+    /// Check if DTSTART falls into a DST transition of its timezone, i.e. names a wall clock time
+    /// that is skipped or occurs twice
+    pub fn has_dst_anomaly(&self) -> bool {
+        let dtstart = match self.get_dtstart() {
+            Some(dtstart) => dtstart,
+            None => return false,
+        };
+        if dtstart.is_date() {
+            return false;
+        }
+        let timezone = match dtstart.get_timezone() {
+            Some(timezone) => timezone,
+            None => return false,
+        };
+
+        // the wall clock time, read as if it was UTC
+        let wall_clock = match dtstart.to_naive_datetime() {
+            Some(wall_clock) => wall_clock.timestamp(),
+            None => return false,
+        };
+        let offset_before = timezone.get_offset_at_timestamp(wall_clock - 24 * 60 * 60);
+        let offset_after = timezone.get_offset_at_timestamp(wall_clock + 24 * 60 * 60);
+        if offset_before == offset_after {
+            return false;
+        }
+
+        // count the instants that display as the wall clock time: none means the time was
+        // skipped, two means it is ambiguous
+        let instants = [offset_before, offset_after]
+            .iter()
+            .map(|offset| wall_clock - i64::from(*offset))
+            .filter(|instant| {
+                instant + i64::from(timezone.get_offset_at_timestamp(*instant)) == wall_clock
+            })
+            .count();
+        instants != 1
+    }
+
+    /// A 64-bit FNV-1a hash of the event's normalized serialization, as hex. DTSTAMP and
+    /// LAST-MODIFIED are left out, so saving an event again without changing it keeps the hash.
+    pub fn content_hash(&self) -> String {
+        let volatile_kinds = [
+            ical::icalproperty_kind_ICAL_DTSTAMP_PROPERTY,
+            ical::icalproperty_kind_ICAL_LASTMODIFIED_PROPERTY,
+        ];
+        let serialized = unsafe {
+            let comp = ical::icalcomponent_new_clone(self.ptr);
+            for kind in &volatile_kinds {
+                let mut prop = ical::icalcomponent_get_first_property(comp, *kind);
+                while !prop.is_null() {
+                    ical::icalcomponent_remove_property(comp, prop);
+                    ical::icalproperty_free(prop);
+                    prop = ical::icalcomponent_get_first_property(comp, *kind);
+                }
+            }
+            ical::icalcomponent_normalize(comp);
+            let serialized = CStr::from_ptr(ical::icalcomponent_as_ical_string(comp))
+                .to_string_lossy()
+                .into_owned();
+            ical::icalcomponent_free(comp);
+            serialized
+        };
+
+        format!("{:016x}", misc::fnv1a_64(serialized.as_bytes()))
+    }
+
     pub fn is_allday(&self) -> bool {
         unsafe {
             let dtstart = ical::icalcomponent_get_dtstart(self.ptr);
@@ -212,7 +509,7 @@ mod tests {
     fn test_get_all_properties() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
 
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
         let props = event.get_properties_all();
         assert_eq!(7, props.len());
     }
@@ -220,7 +517,7 @@ mod tests {
     #[test]
     fn test_get_property_get_value() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY_ALLDAY, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
         let prop = event.get_properties_by_name("DTSTART");
 
         assert_eq!(1, prop.len());
@@ -235,7 +532,7 @@ mod tests {
     #[test]
     fn test_get_property_debug() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY_ALLDAY, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
         let prop = event
             .get_property(ical::icalproperty_kind_ICAL_DTSTART_PROPERTY)
             .unwrap();
@@ -243,10 +540,19 @@ mod tests {
         assert_eq!("DTSTART;VALUE=DATE:20070628", format!("{:?}", prop));
     }
 
+    #[test]
+    fn test_has_property_recurrence_id() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_RECUR_OVERRIDE, None).unwrap();
+        let mut events = cal.events_iter();
+
+        assert!(events.next().unwrap().has_property_recurrence_id());
+        assert!(!events.next().unwrap().has_property_recurrence_id());
+    }
+
     #[test]
     fn test_get_summary() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
 
         assert_eq!(
             Some("Festival International de Jazz de Montreal".to_string()),
@@ -257,15 +563,35 @@ mod tests {
     #[test]
     fn test_get_summary_none() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_NO_SUMMARY, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
 
         assert_eq!(None, event.get_summary());
     }
 
+    #[test]
+    fn test_summary_matches() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None)
+            .unwrap()
+            .with_summary("Festival International de Jazz de Montréal");
+        let event = cal.get_principal_event().unwrap();
+
+        assert!(event.summary_matches("montreal"));
+        assert!(event.summary_matches("JAZZ DE MONTRÉAL"));
+        assert!(!event.summary_matches("quebec"));
+    }
+
+    #[test]
+    fn test_summary_matches_none() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_NO_SUMMARY, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        assert!(!event.summary_matches("montreal"));
+    }
+
     #[test]
     fn test_get_dtstart() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
 
         assert_eq!(
             IcalTime::floating_ymd(2007, 06, 28).and_hms(13, 29, 00),
@@ -276,15 +602,98 @@ mod tests {
     #[test]
     fn test_get_dtstart_negative() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_NO_DTSTART, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
 
         assert!(event.get_dtstart().is_none());
     }
 
+    #[test]
+    fn test_get_recur_datetimes_no_dtstart() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_NO_DTSTART, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        assert!(event.get_recur_datetimes().is_empty());
+    }
+
+    #[test]
+    fn test_get_exdates() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_RECUR_EXDATE, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        assert_eq!(
+            vec![
+                IcalTime::floating_ymd(2018, 10, 18),
+                IcalTime::floating_ymd(2018, 10, 25),
+                IcalTime::floating_ymd(2018, 11, 15),
+            ],
+            event.get_exdates()
+        );
+    }
+
+    #[test]
+    fn test_get_recur_datetimes_exdates() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_RECUR_EXDATE, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        let recur_datetimes = event.get_recur_datetimes();
+
+        assert_eq!(7, recur_datetimes.len());
+        for exdate in event.get_exdates() {
+            assert!(!recur_datetimes.contains(&exdate));
+        }
+    }
+
+    #[test]
+    fn test_get_recur_datetimes_cached() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_RECUR, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+        let computations = || RECUR_COMPUTATIONS.with(|count| count.get());
+        let before = computations();
+
+        let first = event.get_recur_datetimes();
+        assert_eq!(before + 1, computations());
+        let second = event.get_recur_datetimes();
+        assert_eq!(before + 1, computations());
+        assert_eq!(first, second);
+
+        let fresh = event.shallow_copy().get_recur_datetimes();
+        assert_eq!(before + 2, computations());
+        assert_eq!(fresh, first);
+        assert_eq!(10, fresh.len());
+    }
+
+    #[test]
+    fn test_get_recur_datetimes_cache_unrelated_change() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_RECUR, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+        let computations = || RECUR_COMPUTATIONS.with(|count| count.get());
+        let before = computations();
+
+        event.get_recur_datetimes();
+        event.add_category("WORK");
+        event.get_recur_datetimes();
+
+        assert_eq!(before + 1, computations());
+    }
+
+    #[test]
+    fn test_get_recur_datetimes_cache_invalidated() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_RECUR_EXDATE, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+        let computations = || RECUR_COMPUTATIONS.with(|count| count.get());
+        let before = computations();
+
+        assert_eq!(7, event.get_recur_datetimes().len());
+        event.remove_properties_by_name("EXDATE");
+        assert_eq!(10, event.get_recur_datetimes().len());
+
+        assert_eq!(before + 2, computations());
+    }
+
     #[test]
     fn test_get_dtend() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
 
         assert_eq!(
             IcalTime::floating_ymd(2007, 7, 9).and_hms(7, 29, 00),
@@ -295,7 +704,7 @@ mod tests {
     #[test]
     fn test_get_dtend_negative() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_NO_DTSTART, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
 
         assert!(event.get_dtend().is_none());
     }
@@ -303,7 +712,7 @@ mod tests {
     #[test]
     fn test_get_duration_internal_normal() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
 
         assert_eq!(
             Some(IcalDuration::from_seconds(10 * 24 * 60 * 60 + 18 * 60 * 60)),
@@ -314,7 +723,7 @@ mod tests {
     #[test]
     fn test_get_duration_normal() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
 
         assert_eq!(
             Some(IcalDuration::from_seconds(10 * 24 * 60 * 60 + 18 * 60 * 60)),
@@ -325,7 +734,7 @@ mod tests {
     #[test]
     fn test_get_duration_inernal_startdate_only() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_DTSTART_ONLY_DATE, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
 
         assert!(event.get_duration_internal().is_none());
     }
@@ -333,7 +742,7 @@ mod tests {
     #[test]
     fn test_get_duration_startdate_only() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_DTSTART_ONLY_DATE, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
 
         assert_eq!(
             Some(IcalDuration::from_seconds(24 * 60 * 60)),
@@ -344,7 +753,7 @@ mod tests {
     #[test]
     fn test_get_duration_internal_startdatetime_only() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_DTSTART_ONLY_DATETIME, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
 
         assert!(event.get_duration_internal().is_none());
     }
@@ -352,15 +761,41 @@ mod tests {
     #[test]
     fn test_get_duration_startdatetime_only() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_DTSTART_ONLY_DATETIME, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
 
         assert_eq!(Some(IcalDuration::from_seconds(0)), event.get_duration());
     }
 
+    #[test]
+    fn test_get_end_startdate_only() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_DTSTART_ONLY_DATE, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        let end = event.get_end().unwrap();
+        assert!(end.is_date());
+        assert_eq!(IcalTime::floating_ymd(2007, 6, 29), end);
+    }
+
+    #[test]
+    fn test_get_end_startdatetime_only() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_DTSTART_ONLY_DATETIME, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        assert_eq!(event.get_dtstart(), event.get_end());
+    }
+
+    #[test]
+    fn test_get_end_negative() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_NO_DTSTART, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        assert!(event.get_end().is_none());
+    }
+
     #[test]
     fn test_get_description() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_ONE_MEETING, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
 
         assert_eq!(
       Some(
@@ -374,15 +809,260 @@ mod tests {
     #[test]
     fn test_get_description_none() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
 
         assert_eq!(None, event.get_description());
     }
 
+    #[test]
+    fn test_get_comments() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_COMMENTS, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        assert_eq!(
+            vec!["Bring your own snacks.", "Second floor, room 204."],
+            event.get_comments()
+        );
+    }
+
+    #[test]
+    fn test_get_comments_none() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        assert!(event.get_comments().is_empty());
+    }
+
+    #[test]
+    fn test_get_related_to() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_RELATED_TO, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        assert_eq!(
+            vec![
+                ("parent@example.com".to_string(), "PARENT".to_string()),
+                ("sibling@example.com".to_string(), "SIBLING".to_string()),
+                ("implicit@example.com".to_string(), "PARENT".to_string()),
+            ],
+            event.get_related_to()
+        );
+    }
+
+    #[test]
+    fn test_get_related_to_none() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        assert!(event.get_related_to().is_empty());
+    }
+
+    #[test]
+    fn test_get_categories() {
+        let cal =
+            IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTI_CATEGORIES, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        assert_eq!(vec!["MEETING", "PROJECT", "WORK"], event.get_categories());
+    }
+
+    #[test]
+    fn test_add_category() {
+        let cal =
+            IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTI_CATEGORIES, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        event.add_category("PRIVATE");
+        event.add_category("WORK");
+
+        assert_eq!(
+            vec!["MEETING", "PROJECT", "WORK", "PRIVATE"],
+            event.get_categories()
+        );
+        assert_eq!(1, event.get_properties_by_name("CATEGORIES").len());
+    }
+
+    #[test]
+    fn test_remove_category() {
+        let cal =
+            IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTI_CATEGORIES, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        event.remove_category("PROJECT");
+
+        assert_eq!(vec!["MEETING", "WORK"], event.get_categories());
+    }
+
+    #[test]
+    fn test_add_remove_category_keeps_other_properties() {
+        let cal =
+            IcalVCalendar::from_str(testing::data::TEST_EVENT_LEADING_CATEGORIES, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        event.add_category("PRIVATE");
+        event.remove_category("PROJECT");
+
+        assert_eq!(vec!["MEETING", "WORK", "PRIVATE"], event.get_categories());
+        assert_leading_categories_untouched(&event);
+    }
+
+    /// checks the properties following CATEGORIES in TEST_EVENT_LEADING_CATEGORIES
+    fn assert_leading_categories_untouched(event: &IcalVEvent) {
+        assert_eq!(
+            IcalTime::floating_ymd(2007, 6, 28).and_hms(13, 29, 0),
+            event.get_dtstart().unwrap()
+        );
+        assert_eq!(
+            IcalTime::floating_ymd(2007, 6, 28).and_hms(14, 29, 0),
+            event.get_dtend().unwrap()
+        );
+        assert_eq!("Event with categories first", event.get_summary().unwrap());
+        assert_eq!(
+            "FREQ=WEEKLY;COUNT=3",
+            event.get_property_by_name("RRULE").unwrap().get_value()
+        );
+    }
+
+    #[test]
+    fn test_set_categories() {
+        let cal =
+            IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTI_CATEGORIES, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        event.set_categories(&["HOLIDAY", "FAMILY", "TRAVEL"]);
+
+        let props = event.get_properties_by_name("CATEGORIES");
+        assert_eq!(1, props.len());
+        assert_eq!("HOLIDAY,FAMILY,TRAVEL", props[0].get_value());
+        assert_eq!(vec!["HOLIDAY", "FAMILY", "TRAVEL"], event.get_categories());
+    }
+
+    #[test]
+    fn test_set_categories_keeps_other_properties() {
+        let cal =
+            IcalVCalendar::from_str(testing::data::TEST_EVENT_LEADING_CATEGORIES, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        event.set_categories(&["HOLIDAY", "FAMILY"]);
+
+        assert_eq!(1, event.get_properties_by_name("CATEGORIES").len());
+        assert_eq!(vec!["HOLIDAY", "FAMILY"], event.get_categories());
+        assert_leading_categories_untouched(&event);
+    }
+
+    #[test]
+    fn test_get_attachments() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_ATTACHMENTS, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        assert_eq!(
+            vec![
+                Attachment::Uri("ftp://example.com/pub/conf/bkgrnd.ps".to_string()),
+                Attachment::Binary("SGVsbG8gV29ybGQ=".to_string()),
+            ],
+            event.get_attachments()
+        );
+    }
+
+    #[test]
+    fn test_get_attachments_none() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        assert!(event.get_attachments().is_empty());
+    }
+
+    #[test]
+    fn test_is_busy_transp() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        assert!(!event.is_busy());
+    }
+
+    #[test]
+    fn test_is_busy_busystatus_free() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_BUSYSTATUS_FREE, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        assert!(!event.is_busy());
+    }
+
+    #[test]
+    fn test_is_busy_busystatus_busy() {
+        let cal =
+            IcalVCalendar::from_str(testing::data::TEST_EVENT_WITH_X_LIC_ERROR, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        assert!(event.is_busy());
+    }
+
+    #[test]
+    fn test_has_dst_anomaly_nonexistent() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_DST_GAP, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        assert!(event.has_dst_anomaly());
+    }
+
+    #[test]
+    fn test_has_dst_anomaly_ambiguous() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_DST_GAP, None)
+            .unwrap()
+            .with_dtstart(&IcalTime::floating_ymd(2019, 10, 27).and_hms(2, 30, 0));
+        let event = cal.get_principal_event().unwrap();
+
+        assert!(event.has_dst_anomaly());
+    }
+
+    #[test]
+    fn test_has_dst_anomaly_negative() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_DST_GAP, None)
+            .unwrap()
+            .with_dtstart(&IcalTime::floating_ymd(2019, 3, 31).and_hms(3, 30, 0));
+        let event = cal.get_principal_event().unwrap();
+
+        assert!(!event.has_dst_anomaly());
+    }
+
+    #[test]
+    fn test_content_hash_ignores_dtstamp() {
+        let restamped = testing::data::TEST_EVENT_MULTIDAY
+            .replace("DTSTAMP:20070423T123432Z", "DTSTAMP:20200101T000000Z");
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
+        let restamped_cal = IcalVCalendar::from_str(&restamped, None).unwrap();
+
+        assert_eq!(
+            cal.get_principal_event().unwrap().content_hash(),
+            restamped_cal.get_principal_event().unwrap().content_hash()
+        );
+    }
+
+    #[test]
+    fn test_content_hash_known_value() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MINIMAL, None).unwrap();
+        let event = cal.get_principal_event().unwrap();
+
+        // FNV-1a of "BEGIN:VEVENT\r\nUID:minimalevent\r\nEND:VEVENT\r\n"
+        assert_eq!("a23a9f00f45155db", event.content_hash());
+    }
+
+    #[test]
+    fn test_content_hash_summary() {
+        let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None).unwrap();
+        let changed_cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_MULTIDAY, None)
+            .unwrap()
+            .with_summary("Another summary");
+
+        assert_ne!(
+            cal.get_principal_event().unwrap().content_hash(),
+            changed_cal.get_principal_event().unwrap().content_hash()
+        );
+    }
+
     #[test]
     fn test_get_location() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_EVENT_ONE_MEETING, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
 
         assert_eq!(Some("LDB Lobby".to_string()), event.get_location());
     }
@@ -390,7 +1070,7 @@ mod tests {
     #[test]
     fn test_get_location_none() {
         let cal = IcalVCalendar::from_str(testing::data::TEST_NO_SUMMARY, None).unwrap();
-        let event = cal.get_principal_event();
+        let event = cal.get_principal_event().unwrap();
 
         assert_eq!(None, event.get_location());
     }